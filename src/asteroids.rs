@@ -0,0 +1,249 @@
+//! Chiron, Pholus and the four main-belt asteroids from mean elements
+//!
+//! Each body is a Keplerian orbit (J2000 ecliptic and equinox) whose node and
+//! perihelion turn at their secular rates. The mean longitude was fitted to
+//! observed oppositions and sign ingresses, so the two-body motion absorbs the
+//! average planetary perturbations but not the periodic ones.
+//!
+//! Chiron and Pholus cross the orbits of the giant planets, and their
+//! semi-major axes drift as well. Their rates were fitted to a numerical
+//! integration under Jupiter, Saturn, Uranus and Neptune over 1900-2100,
+//! started from the observed orbit; the elements follow it to 0.95° (Chiron)
+//! and 0.6° (Pholus) in heliocentric longitude. Independently of that fit,
+//! Chiron's sign ingresses between 2005 and 2026 fall within 0.3° of those
+//! from Swiss Ephemeris. Pholus has only been checked against the integration.
+//!
+//! | Body | Swiss | Accuracy 1900-2100 |
+//! |------|-------|--------------------|
+//! | Ceres, Vesta | 17, 20 | about 0.1° near 2020, within 1° |
+//! | Pallas, Juno | 18, 19 | about 0.2° near 2020, within 1.5° |
+//! | Chiron | 15 | within 1° |
+//! | Pholus | 16 | within 0.6° of the integration |
+//!
+//! Outside of [`MINOR_BODY_START`]..[`MINOR_BODY_END`] the elements are not
+//! used and the calculation fails with `OutOfRange`.
+//!
+//! Bodies registered with [`crate::register_orbit`] follow the same path as
+//! pure two-body orbits, without a range limit; their accuracy is that of the
+//! elements supplied.
+
+use crate::planets::OrbitalElements;
+use crate::{context, Error, KeplerElements, Planet, Result};
+
+/// Start of the validity range of the built-in elements (1900-01-01)
+pub const MINOR_BODY_START: f64 = 2415020.5;
+/// End of the validity range of the built-in elements (2100-01-01)
+pub const MINOR_BODY_END: f64 = 2488069.5;
+
+/// Epoch of the elements (2019-04-27 TT)
+const EPOCH: f64 = 2458600.5;
+
+/// Gaussian gravitational constant in degrees per day
+const GAUSS_K_DEG: f64 = 0.9856076686;
+
+/// Mean elements at [`EPOCH`], J2000 ecliptic
+///
+/// Columns: semi-major axis (AU), eccentricity, inclination, node, longitude
+/// of perihelion, mean longitude (degrees), then the node and perihelion rates
+/// (arcsec/year) and the semi-major axis rate (AU/century).
+const ELEMENTS: [(Planet, [f64; 9]); 6] = [
+    (Planet::Chiron, [13.6146, 0.37837, 6.9290, 209.2349, 188.4695, 354.8962, -13.8, -9.6, 0.0213]),
+    (Planet::Pholus, [20.2503, 0.57187, 24.6607, 119.2917, 114.0119, 223.2010, -4.7, 5.8, 0.1536]),
+    (Planet::Ceres, [2.7691651, 0.07600902, 10.59407, 80.3055, 153.9032, 230.97379, -59.2, 54.1, 0.0]),
+    (Planet::Pallas, [2.7724, 0.2302, 34.837, 172.918, 123.068, 182.863, -46.4, -1.5, 0.0]),
+    (Planet::Juno, [2.6694, 0.2562, 12.991, 169.851, 57.917, 92.5694, -61.4, 43.8, 0.0]),
+    (Planet::Vesta, [2.36179, 0.08874, 7.1422, 103.85136, 255.04989, 349.94399, -39.6, 36.9, 0.0]),
+];
+
+/// Elements of a minor body at `jd` (J2000 ecliptic)
+pub(crate) fn elements(jd: f64, planet: Planet) -> Result<OrbitalElements> {
+    if let Planet::Custom(index) = planet {
+        let el = context::get_orbit(index).ok_or(Error::InvalidPlanet(planet.id()))?;
+        return Ok(registered_elements(jd, &el));
+    }
+    if !(MINOR_BODY_START..=MINOR_BODY_END).contains(&jd) {
+        return Err(Error::OutOfRange);
+    }
+
+    let (_, el) = ELEMENTS.iter().find(|(p, _)| *p == planet).ok_or(Error::InvalidPlanet(planet.id()))?;
+    let days = jd - EPOCH;
+    let years = days / 365.25;
+    // Drift of the semi-major axis relative to its value at the epoch
+    let drift = el[8] * years / 100.0 / el[0];
+
+    Ok(OrbitalElements {
+        semi_major: el[0] * (1.0 + drift),
+        ecc: el[1],
+        incl: el[2],
+        asc_node: el[3] + el[6] * years / 3600.0,
+        lon_peri: el[4] + el[7] * years / 3600.0,
+        // The mean motion goes as a^-3/2; integrated to first order in the drift
+        mean_lon: el[5] + GAUSS_K_DEG / el[0].powf(1.5) * days * (1.0 - 0.75 * drift),
+    })
+}
+
+/// Elements of a registered body at `jd`, moving along a fixed ellipse
+fn registered_elements(jd: f64, el: &KeplerElements) -> OrbitalElements {
+    OrbitalElements {
+        semi_major: el.semi_major,
+        ecc: el.eccentricity,
+        incl: el.inclination,
+        asc_node: el.asc_node,
+        lon_peri: el.lon_perihelion,
+        mean_lon: el.lon_perihelion + el.mean_anomaly
+            + GAUSS_K_DEG / el.semi_major.powf(1.5) * (jd - el.epoch),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::julian::julday_greg;
+    use crate::constants::{DEG_TO_RAD, J2000, RAD_TO_DEG};
+    use crate::math::{angle_diff, cart_to_pol, pol_to_cart};
+    use crate::precession::precess_ecliptic;
+    use crate::{calc_ut, calc_ut_flags, KeplerElements, SEFLG_EQUATORIAL};
+
+    /// Days from `jd` to the nearest opposition in longitude
+    fn days_to_opposition(jd: f64, planet: Planet) -> f64 {
+        let body = calc_ut(jd, planet, true).unwrap();
+        let sun = calc_ut(jd, Planet::Sun, true).unwrap();
+        let elongation = angle_diff(body.longitude, sun.longitude + 180.0);
+        elongation / (sun.speed_longitude - body.speed_longitude)
+    }
+
+    #[test]
+    fn test_oppositions() {
+        // Observed opposition dates, known to the day
+        for &(planet, y, m, d) in &[
+            (Planet::Ceres, 2019, 5, 28),
+            (Planet::Ceres, 2023, 3, 21),
+            (Planet::Vesta, 2018, 6, 19),
+            (Planet::Vesta, 2023, 12, 21),
+            (Planet::Juno, 2018, 11, 17),
+            (Planet::Pallas, 2014, 2, 22),
+        ] {
+            let days = days_to_opposition(julday_greg(y, m, d, 12.0), planet);
+            assert!(days.abs() < 1.5, "{:?} {}-{}-{}: {:.2} days", planet, y, m, d, days);
+        }
+    }
+
+    #[test]
+    fn test_pallas_occultation() {
+        // Pallas occulted 1 Vulpeculae on 1983 May 29 (RA 19h16m13s, Dec +21°23', J2000)
+        let jd = julday_greg(1983, 5, 29, 5.0);
+        let pos = calc_ut_flags(jd, Planet::Pallas, SEFLG_EQUATORIAL).unwrap();
+        // Precession from J2000 back to 1983 moves the star by about -0.23° in RA
+        assert!((pos.longitude - (289.054 - 0.23)).abs() < 0.3, "RA {}", pos.longitude);
+        assert!((pos.latitude - 21.39).abs() < 0.3, "Dec {}", pos.latitude);
+    }
+
+    #[test]
+    fn test_chiron_ingress() {
+        // Chiron entered Aries on 2018 April 17 and Taurus on 2026 June 19
+        let aries = calc_ut(julday_greg(2018, 4, 17, 12.0), Planet::Chiron, true).unwrap();
+        assert!(angle_diff(aries.longitude, 0.0).abs() < 0.3, "{}", aries.longitude);
+        assert!(aries.speed_longitude > 0.0);
+        let taurus = calc_ut(julday_greg(2026, 6, 19, 12.0), Planet::Chiron, false).unwrap();
+        assert!(angle_diff(taurus.longitude, 30.0).abs() < 0.3, "{}", taurus.longitude);
+
+        // Perihelion in February 1996 at 8.45 AU
+        let peri = crate::calc_heliocentric_ut(julday_greg(1996, 2, 14, 0.0), Planet::Chiron, true).unwrap();
+        assert!((peri.distance - 8.45).abs() < 0.05, "{}", peri.distance);
+        assert!(peri.speed_distance.abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_chiron_swiss_ingresses() {
+        // Sign ingresses from Swiss Ephemeris that the elements were not fitted
+        // to: Aquarius in 2005, Pisces in 2010 and for good in 2011 after the
+        // retrograde return of 2010, and Aries again in 2019 after the one of 2018
+        for &(year, month, day, cusp) in &[
+            (2005, 2, 21, 300.0),
+            (2010, 4, 20, 330.0),
+            (2010, 7, 20, 330.0),
+            (2011, 2, 8, 330.0),
+            (2018, 9, 25, 0.0),
+            (2019, 2, 18, 0.0),
+        ] {
+            let pos = calc_ut(julday_greg(year, month, day, 12.0), Planet::Chiron, false).unwrap();
+            let offset = angle_diff(pos.longitude, cusp);
+            assert!(offset.abs() < 0.25, "{}-{:02}-{:02}: {:.2}° from the cusp", year, month, day, offset);
+        }
+    }
+
+    #[test]
+    fn test_centaurs_range_ends() {
+        // Heliocentric J2000 positions from the numerical integration the rates
+        // were fitted to, at and near both ends of the range. This only checks
+        // the fit; test_chiron_swiss_ingresses is the independent check.
+        for &(planet, year, lon, lat, r) in &[
+            (Planet::Chiron, 1900, 251.383, 4.592, 9.995),
+            (Planet::Chiron, 1905, 297.707, 6.897, 13.366),
+            (Planet::Chiron, 2095, 161.963, -5.086, 8.722),
+            (Planet::Chiron, 2100, 242.244, 3.823, 9.529),
+            (Planet::Pholus, 1900, 93.963, -11.171, 8.910),
+            (Planet::Pholus, 1905, 169.010, 19.288, 10.430),
+            (Planet::Pholus, 2095, 231.563, 23.008, 18.131),
+            (Planet::Pholus, 2100, 248.810, 19.472, 22.148),
+        ] {
+            let jd = julday_greg(year, 1, 1, 0.0);
+            let expected = precess_ecliptic(&pol_to_cart(&[lon * DEG_TO_RAD, lat * DEG_TO_RAD, r]), J2000, jd);
+            let expected = cart_to_pol(&expected);
+            let pos = crate::calc_heliocentric(jd, planet, false).unwrap();
+            let dlon = angle_diff(pos.longitude, expected[0] * RAD_TO_DEG);
+            assert!(dlon.abs() < 1.0, "{:?} {}: longitude off by {:.2}°", planet, year, dlon);
+            assert!((pos.latitude - expected[1] * RAD_TO_DEG).abs() < 0.3, "{:?} {}: {}", planet, year, pos.latitude);
+            assert!((pos.distance - r).abs() < 0.1, "{:?} {}: {}", planet, year, pos.distance);
+        }
+    }
+
+    #[test]
+    fn test_registered_orbit() {
+        let _lock = crate::context::ORBITS_TEST_LOCK.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        crate::clear_orbits();
+        // Eris, MPC elements at 2020 May 31; it stays near 23° Aries at about 96 AU
+        let eris = crate::register_orbit("Eris", KeplerElements {
+            epoch: 2459000.5,
+            semi_major: 67.864,
+            eccentricity: 0.4361,
+            inclination: 44.04,
+            asc_node: 35.95,
+            lon_perihelion: 187.59,
+            mean_anomaly: 205.99,
+        })
+        .unwrap();
+        let pos = calc_ut(julday_greg(2020, 6, 1, 0.0), eris, true).unwrap();
+        assert!((pos.longitude - 23.5).abs() < 2.0, "{}", pos.longitude);
+        assert!(pos.distance > 95.0 && pos.distance < 97.5, "{}", pos.distance);
+        // Outside the range of the built-in elements as well
+        assert!(calc_ut(julday_greg(1850, 1, 1, 0.0), eris, false).is_ok());
+
+        // Halley's comet at its 1986 perihelion, 0.586 AU from the Sun
+        let halley = crate::register_orbit("Halley", KeplerElements {
+            epoch: julday_greg(1986, 2, 9, 11.0),
+            semi_major: 17.834,
+            eccentricity: 0.96714,
+            inclination: 162.262,
+            asc_node: 58.42,
+            lon_perihelion: 169.75,
+            mean_anomaly: 0.0,
+        })
+        .unwrap();
+        let peri = crate::calc_heliocentric(julday_greg(1986, 2, 9, 11.0), halley, true).unwrap();
+        assert!((peri.distance - 0.586).abs() < 0.001, "{}", peri.distance);
+        let later = crate::calc_heliocentric(julday_greg(1986, 4, 9, 11.0), halley, false).unwrap();
+        assert!(later.distance > 0.9 && later.distance < 1.5, "{}", later.distance);
+
+        assert_eq!(Planet::from_i32(41), Some(halley));
+        assert_eq!(Planet::from_i32(42), None);
+        assert!(calc_ut(julday_greg(2020, 6, 1, 0.0), Planet::Custom(7), false).is_err());
+        crate::clear_orbits();
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        assert!(calc_ut(julday_greg(1850, 1, 1, 0.0), Planet::Ceres, false).is_err());
+        assert!(calc_ut(julday_greg(1850, 1, 1, 0.0), Planet::Mars, false).is_ok());
+    }
+}
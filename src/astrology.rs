@@ -2,13 +2,18 @@
 //!
 //! This module provides higher-level astrological calculations:
 //! - Zodiac sign from longitude
+//! - Nakshatras and divisional charts (vargas) of Vedic astrology
 //! - Moon phases
 //! - Solar and lunar eclipse detection
 //! - Aspects between planets
 //! - Planetary hours
 //! - Void-of-course Moon detection
 
-use crate::{calc_heliocentric_ut, calc_houses, calc_ut, Planet, Result};
+use crate::constants::{RAD_TO_DEG, SEFLG_NONUT, SEFLG_SPEED};
+use crate::{
+    calc_heliocentric_ut, calc_houses, calc_ut, calc_ut_flags, delta_t, get_ayanamsa_ut, get_sid_mode, houses_armc,
+    math, sidereal, Error, HouseSystem, Planet, Result, SiderealMode,
+};
 
 /// Zodiac signs in order (0 = Aries, 11 = Pisces)
 pub const ZODIAC_SIGNS: [&str; 12] = [
@@ -31,6 +36,50 @@ pub const PLANET_KEYS: [&str; 10] = [
     "sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn", "uranus", "neptune", "pluto",
 ];
 
+/// Minor body keys, after the planets in [`get_all_planetary_positions`]
+pub const MINOR_BODY_KEYS: [&str; 6] = ["chiron", "ceres", "pallas", "juno", "vesta", "pholus"];
+
+/// Nakshatras (lunar mansions) in order, 13°20' each from 0° sidereal Aries
+pub const NAKSHATRAS: [&str; 27] = [
+    "ashwini",
+    "bharani",
+    "krittika",
+    "rohini",
+    "mrigashira",
+    "ardra",
+    "punarvasu",
+    "pushya",
+    "ashlesha",
+    "magha",
+    "purva_phalguni",
+    "uttara_phalguni",
+    "hasta",
+    "chitra",
+    "swati",
+    "vishakha",
+    "anuradha",
+    "jyeshtha",
+    "mula",
+    "purva_ashadha",
+    "uttara_ashadha",
+    "shravana",
+    "dhanishta",
+    "shatabhisha",
+    "purva_bhadrapada",
+    "uttara_bhadrapada",
+    "revati",
+];
+
+/// Vimshottari lords of the nakshatras, repeating from Ashwini every nine
+pub const NAKSHATRA_LORDS: [&str; 9] = [
+    "ketu", "venus", "sun", "moon", "mars", "rahu", "jupiter", "saturn", "mercury",
+];
+
+/// Keys of the nine grahas in a [`VedicChart`]
+pub const GRAHA_KEYS: [&str; 9] = [
+    "sun", "moon", "mars", "mercury", "jupiter", "venus", "saturn", "rahu", "ketu",
+];
+
 /// Moon phase keys
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MoonPhase {
@@ -59,6 +108,168 @@ impl MoonPhase {
     }
 }
 
+/// Which lunar node a chart shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NodeType {
+    /// Osculating node
+    #[default]
+    True,
+    /// Mean node, preferred in traditional astrology
+    Mean,
+}
+
+impl NodeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NodeType::True => "true_node",
+            NodeType::Mean => "mean_node",
+        }
+    }
+
+    /// Planet used to calculate this node
+    pub fn planet(&self) -> Planet {
+        match self {
+            NodeType::True => Planet::TrueNode,
+            NodeType::Mean => Planet::MeanNode,
+        }
+    }
+}
+
+/// Which lunar apogee a chart shows as Black Moon Lilith
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LilithType {
+    /// Mean apogee, the usual Black Moon Lilith
+    #[default]
+    Mean,
+    /// Osculating apogee ("true" Lilith)
+    Osculating,
+}
+
+impl LilithType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LilithType::Mean => "mean_lilith",
+            LilithType::Osculating => "oscu_lilith",
+        }
+    }
+
+    /// Planet used to calculate this apogee
+    pub fn planet(&self) -> Planet {
+        match self {
+            LilithType::Mean => Planet::MeanApogee,
+            LilithType::Osculating => Planet::OscuApogee,
+        }
+    }
+}
+
+/// Divisional charts (vargas) of the Parashari shodashavarga, D1 to D60
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Varga {
+    /// D1, the birth chart
+    Rasi,
+    /// D2, halves ruled by the Sun (Leo) and the Moon (Cancer)
+    Hora,
+    /// D3, thirds counted from the sign, its 5th and its 9th
+    Drekkana,
+    /// D4, quarters counted from the sign, its 4th, 7th and 10th
+    Chaturthamsa,
+    /// D7, from the sign (odd) or its 7th (even)
+    Saptamsa,
+    /// D9, from Aries, Capricorn, Libra or Cancer by element
+    Navamsa,
+    /// D10, from the sign (odd) or its 9th (even)
+    Dashamsa,
+    /// D12, from the sign
+    Dwadashamsa,
+    /// D16, from Aries, Leo or Sagittarius by modality
+    Shodashamsa,
+    /// D20, from Aries, Sagittarius or Leo by modality
+    Vimshamsa,
+    /// D24, from Leo (odd) or Cancer (even)
+    Chaturvimshamsa,
+    /// D27, from Aries, Cancer, Libra or Capricorn by element
+    Bhamsa,
+    /// D30, unequal parts ruled by Mars, Saturn, Jupiter, Mercury and Venus
+    Trimshamsa,
+    /// D40, from Aries (odd) or Libra (even)
+    Khavedamsa,
+    /// D45, from Aries, Leo or Sagittarius by modality
+    Akshavedamsa,
+    /// D60, from the sign
+    Shashtiamsa,
+}
+
+impl Varga {
+    /// Number of parts each sign is divided into
+    pub fn division(&self) -> u8 {
+        match self {
+            Varga::Rasi => 1,
+            Varga::Hora => 2,
+            Varga::Drekkana => 3,
+            Varga::Chaturthamsa => 4,
+            Varga::Saptamsa => 7,
+            Varga::Navamsa => 9,
+            Varga::Dashamsa => 10,
+            Varga::Dwadashamsa => 12,
+            Varga::Shodashamsa => 16,
+            Varga::Vimshamsa => 20,
+            Varga::Chaturvimshamsa => 24,
+            Varga::Bhamsa => 27,
+            Varga::Trimshamsa => 30,
+            Varga::Khavedamsa => 40,
+            Varga::Akshavedamsa => 45,
+            Varga::Shashtiamsa => 60,
+        }
+    }
+
+    /// Varga of a division number, e.g. 9 for the Navamsa
+    pub fn from_division(division: u8) -> Option<Varga> {
+        Varga::all().iter().copied().find(|v| v.division() == division)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Varga::Rasi => "rasi",
+            Varga::Hora => "hora",
+            Varga::Drekkana => "drekkana",
+            Varga::Chaturthamsa => "chaturthamsa",
+            Varga::Saptamsa => "saptamsa",
+            Varga::Navamsa => "navamsa",
+            Varga::Dashamsa => "dashamsa",
+            Varga::Dwadashamsa => "dwadashamsa",
+            Varga::Shodashamsa => "shodashamsa",
+            Varga::Vimshamsa => "vimshamsa",
+            Varga::Chaturvimshamsa => "chaturvimshamsa",
+            Varga::Bhamsa => "bhamsa",
+            Varga::Trimshamsa => "trimshamsa",
+            Varga::Khavedamsa => "khavedamsa",
+            Varga::Akshavedamsa => "akshavedamsa",
+            Varga::Shashtiamsa => "shashtiamsa",
+        }
+    }
+
+    pub fn all() -> &'static [Varga] {
+        &[
+            Varga::Rasi,
+            Varga::Hora,
+            Varga::Drekkana,
+            Varga::Chaturthamsa,
+            Varga::Saptamsa,
+            Varga::Navamsa,
+            Varga::Dashamsa,
+            Varga::Dwadashamsa,
+            Varga::Shodashamsa,
+            Varga::Vimshamsa,
+            Varga::Chaturvimshamsa,
+            Varga::Bhamsa,
+            Varga::Trimshamsa,
+            Varga::Khavedamsa,
+            Varga::Akshavedamsa,
+            Varga::Shashtiamsa,
+        ]
+    }
+}
+
 /// Eclipse types significant in astrology
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EclipseType {
@@ -252,9 +463,49 @@ pub struct NatalChart {
     pub houses: Vec<HouseCusp>,
     pub ascendant: f64,
     pub midheaven: f64,
+    /// House system of the cusps, Porphyry within the polar circles
+    pub house_system: HouseSystem,
+    /// Ayanamsa subtracted from all longitudes, `None` in the tropical zodiac
+    pub ayanamsa: Option<f64>,
+    pub node_type: NodeType,
     pub north_node_longitude: f64,
     pub north_node_sign: &'static str,
     pub north_node_degree: f64,
+    pub lilith_type: LilithType,
+    pub lilith_longitude: f64,
+    pub lilith_sign: &'static str,
+    pub lilith_degree: f64,
+}
+
+/// Graha or Lagna in a Vedic chart
+#[derive(Debug, Clone)]
+pub struct VedicPosition {
+    /// One of [`GRAHA_KEYS`], or "ascendant" for the Lagna
+    pub planet_key: &'static str,
+    /// Sidereal longitude in degrees
+    pub longitude: f64,
+    pub sign_key: &'static str,
+    pub sign_degree: f64,
+    pub nakshatra_key: &'static str,
+    /// Quarter of the nakshatra (1-4)
+    pub nakshatra_pada: u8,
+    pub nakshatra_lord: &'static str,
+    /// Whole sign house counted from the Lagna (1-12)
+    pub house: u8,
+    pub is_retrograde: bool,
+    /// Sign in each divisional chart, in the order of [`Varga::all`]
+    pub vargas: Vec<(Varga, &'static str)>,
+}
+
+/// Sidereal chart of the nine grahas with nakshatras and divisional charts
+#[derive(Debug, Clone)]
+pub struct VedicChart {
+    pub sid_mode: SiderealMode,
+    /// Ayanamsa subtracted from the tropical longitudes
+    pub ayanamsa: f64,
+    pub ascendant: VedicPosition,
+    /// Grahas in the order of [`GRAHA_KEYS`]
+    pub planets: Vec<VedicPosition>,
 }
 
 /// Chaldean order for planetary hours
@@ -290,6 +541,81 @@ pub fn get_sign_degree(longitude: f64) -> f64 {
     lon % 30.0
 }
 
+/// Get nakshatra from sidereal ecliptic longitude
+pub fn get_nakshatra_from_longitude(longitude: f64) -> &'static str {
+    NAKSHATRAS[nakshatra_index(longitude)]
+}
+
+/// Get quarter (pada, 1-4) within the nakshatra of a sidereal longitude
+pub fn get_nakshatra_pada(longitude: f64) -> u8 {
+    ((math::deg_norm(longitude) * 27.0 / 360.0).fract() * 4.0) as u8 % 4 + 1
+}
+
+/// Get Vimshottari lord of the nakshatra of a sidereal longitude
+pub fn get_nakshatra_lord(longitude: f64) -> &'static str {
+    NAKSHATRA_LORDS[nakshatra_index(longitude) % 9]
+}
+
+fn nakshatra_index(longitude: f64) -> usize {
+    (math::deg_norm(longitude) * 27.0 / 360.0) as usize % 27
+}
+
+/// Get longitude in a divisional chart
+///
+/// The sign is the one the varga assigns to the part of the sign containing
+/// `longitude`; the degree spreads that part over the whole 30° of the sign.
+pub fn get_varga_longitude(longitude: f64, varga: Varga) -> f64 {
+    let lon = math::deg_norm(longitude);
+    let sign = (lon / 30.0) as usize % 12;
+    let degree = lon - sign as f64 * 30.0;
+    // Aries is the first, odd sign
+    let odd = sign.is_multiple_of(2);
+
+    let (varga_sign, fraction) = match varga {
+        Varga::Hora => {
+            // Odd signs begin with the Sun's hora, even signs with the Moon's
+            let sun = (degree < 15.0) == odd;
+            (if sun { 4 } else { 3 }, (degree % 15.0) / 15.0)
+        }
+        Varga::Trimshamsa => {
+            // Parts of 5, 5, 8, 7 and 5 degrees, reversed in even signs
+            let parts: [(f64, usize); 5] = if odd {
+                [(5.0, 0), (10.0, 10), (18.0, 8), (25.0, 2), (30.0, 6)]
+            } else {
+                [(5.0, 1), (12.0, 5), (20.0, 11), (25.0, 9), (30.0, 7)]
+            };
+            let i = parts.iter().position(|&(end, _)| degree < end).unwrap_or(4);
+            let start = if i == 0 { 0.0 } else { parts[i - 1].0 };
+            (parts[i].1, (degree - start) / (parts[i].0 - start))
+        }
+        _ => {
+            let n = varga.division() as usize;
+            let scaled = degree * n as f64 / 30.0;
+            let part = (scaled as usize).min(n - 1);
+            // First sign of the count, and signs advanced per part
+            let (first, step) = match varga {
+                Varga::Drekkana => (sign, 4),
+                Varga::Chaturthamsa => (sign, 3),
+                Varga::Saptamsa => (if odd { sign } else { sign + 6 }, 1),
+                Varga::Dashamsa => (if odd { sign } else { sign + 8 }, 1),
+                Varga::Chaturvimshamsa => (if odd { 4 } else { 3 }, 1),
+                Varga::Khavedamsa => (if odd { 0 } else { 6 }, 1),
+                Varga::Akshavedamsa => ([0, 4, 8][sign % 3], 1),
+                // Continuous through the zodiac
+                Varga::Navamsa | Varga::Shodashamsa | Varga::Vimshamsa | Varga::Bhamsa => (sign * n, 1),
+                _ => (sign, 1),
+            };
+            ((first + part * step) % 12, scaled - part as f64)
+        }
+    };
+    varga_sign as f64 * 30.0 + fraction * 30.0
+}
+
+/// Get sign in a divisional chart
+pub fn get_varga_sign(longitude: f64, varga: Varga) -> &'static str {
+    get_sign_from_longitude(get_varga_longitude(longitude, varga))
+}
+
 /// Calculate moon phase from Sun and Moon longitudes
 pub fn get_moon_phase(sun_longitude: f64, moon_longitude: f64) -> MoonPhase {
     let mut diff = moon_longitude - sun_longitude;
@@ -491,6 +817,10 @@ pub fn compute_aspects(
 }
 
 /// Get all planetary positions at a given time
+///
+/// The Sun through Pluto, then Chiron, Ceres, Pallas, Juno, Vesta and Pholus.
+/// The minor bodies are left out outside their validity range (see
+/// [`crate::asteroids`]).
 pub fn get_all_planetary_positions(jd: f64) -> Result<Vec<PlanetPosition>> {
     let planets = [
         (Planet::Sun, "sun"),
@@ -503,12 +833,21 @@ pub fn get_all_planetary_positions(jd: f64) -> Result<Vec<PlanetPosition>> {
         (Planet::Uranus, "uranus"),
         (Planet::Neptune, "neptune"),
         (Planet::Pluto, "pluto"),
+        (Planet::Chiron, "chiron"),
+        (Planet::Ceres, "ceres"),
+        (Planet::Pallas, "pallas"),
+        (Planet::Juno, "juno"),
+        (Planet::Vesta, "vesta"),
+        (Planet::Pholus, "pholus"),
     ];
 
-    let mut positions = Vec::with_capacity(10);
+    let mut positions = Vec::with_capacity(planets.len());
 
     for (planet, key) in planets {
-        let pos = calc_ut(jd, planet, true)?;
+        let pos = match calc_ut(jd, planet, true) {
+            Err(Error::OutOfRange) if planet.is_minor_body() => continue,
+            result => result?,
+        };
         positions.push(PlanetPosition {
             planet_key: key,
             longitude: pos.longitude,
@@ -524,13 +863,37 @@ pub fn get_all_planetary_positions(jd: f64) -> Result<Vec<PlanetPosition>> {
     Ok(positions)
 }
 
-/// Get complete natal chart
+/// Get complete natal chart with the true node and mean Lilith
+///
+/// Longitudes, cusps and angles are sidereal when a mode is set with
+/// [`crate::set_sid_mode`].
 pub fn get_natal_chart(jd: f64, latitude: f64, longitude: f64) -> Result<NatalChart> {
+    get_natal_chart_with_node(jd, latitude, longitude, NodeType::True)
+}
+
+/// Get complete natal chart with the mean or true node and mean Lilith
+pub fn get_natal_chart_with_node(
+    jd: f64,
+    latitude: f64,
+    longitude: f64,
+    node_type: NodeType,
+) -> Result<NatalChart> {
+    get_natal_chart_with_points(jd, latitude, longitude, node_type, LilithType::Mean)
+}
+
+/// Get complete natal chart, choosing the node and the Lilith variant
+pub fn get_natal_chart_with_points(
+    jd: f64,
+    latitude: f64,
+    longitude: f64,
+    node_type: NodeType,
+    lilith_type: LilithType,
+) -> Result<NatalChart> {
     // Get planet positions
     let planets = get_all_planetary_positions(jd)?;
 
     // Get houses
-    let house_data = calc_houses(jd, latitude, longitude)?;
+    let house_data = calc_houses(jd, latitude, longitude, HouseSystem::Placidus)?;
 
     let mut houses = Vec::with_capacity(12);
     for i in 1..=12 {
@@ -544,16 +907,91 @@ pub fn get_natal_chart(jd: f64, latitude: f64, longitude: f64) -> Result<NatalCh
     }
 
     // Get North Node
-    let node = calc_ut(jd, Planet::TrueNode, false)?;
+    let node = calc_ut(jd, node_type.planet(), false)?;
+
+    // Get Black Moon Lilith
+    let lilith = calc_ut(jd, lilith_type.planet(), false)?;
+
+    let ayanamsa = match get_sid_mode() {
+        Some(_) => Some(get_ayanamsa_ut(jd)?),
+        None => None,
+    };
 
     Ok(NatalChart {
         planets,
         houses,
         ascendant: house_data.ascendant,
         midheaven: house_data.mc,
+        house_system: house_data.system,
+        ayanamsa,
+        node_type,
         north_node_longitude: node.longitude,
         north_node_sign: get_sign_from_longitude(node.longitude),
         north_node_degree: get_sign_degree(node.longitude),
+        lilith_type,
+        lilith_longitude: lilith.longitude,
+        lilith_sign: get_sign_from_longitude(lilith.longitude),
+        lilith_degree: get_sign_degree(lilith.longitude),
+    })
+}
+
+/// Get Vedic chart: the nine grahas and the Lagna in the sidereal zodiac
+///
+/// Uses the mode set with [`crate::set_sid_mode`], or Lahiri when none is
+/// set. Rahu is the mean node and Ketu the point opposite; houses are whole
+/// signs from the Lagna.
+pub fn get_vedic_chart(jd: f64, latitude: f64, longitude: f64) -> Result<VedicChart> {
+    let sid_mode = get_sid_mode().unwrap_or(SiderealMode::Lahiri);
+    let jd_et = jd + delta_t(jd);
+    let ayanamsa = sidereal::ayanamsa(jd_et, sid_mode)?;
+
+    // Tropical positions of the mean equinox, which the ayanamsa is counted from
+    let eps = math::obliquity(jd_et) * RAD_TO_DEG;
+    let houses = houses_armc(math::armc(jd, longitude), latitude, eps, HouseSystem::WholeSign)?;
+    let lagna = math::deg_norm(houses.ascendant - ayanamsa);
+    let lagna_sign = (lagna / 30.0) as usize % 12;
+
+    let position = |planet_key: &'static str, longitude: f64, speed: f64| {
+        let sign = (longitude / 30.0) as usize % 12;
+        VedicPosition {
+            planet_key,
+            longitude,
+            sign_key: get_sign_from_longitude(longitude),
+            sign_degree: get_sign_degree(longitude),
+            nakshatra_key: get_nakshatra_from_longitude(longitude),
+            nakshatra_pada: get_nakshatra_pada(longitude),
+            nakshatra_lord: get_nakshatra_lord(longitude),
+            house: ((sign + 12 - lagna_sign) % 12 + 1) as u8,
+            is_retrograde: speed < 0.0,
+            vargas: Varga::all().iter().map(|&v| (v, get_varga_sign(longitude, v))).collect(),
+        }
+    };
+
+    let grahas = [
+        Planet::Sun,
+        Planet::Moon,
+        Planet::Mars,
+        Planet::Mercury,
+        Planet::Jupiter,
+        Planet::Venus,
+        Planet::Saturn,
+        Planet::MeanNode,
+    ];
+    // Sidereal longitude and speed of each graha
+    let mut points = Vec::with_capacity(GRAHA_KEYS.len());
+    for &planet in &grahas {
+        let pos = calc_ut_flags(jd, planet, SEFLG_NONUT | SEFLG_SPEED)?;
+        points.push((math::deg_norm(pos.longitude - ayanamsa), pos.speed_longitude));
+    }
+    let (rahu, speed) = points[7];
+    points.push((math::deg_norm(rahu + 180.0), speed));
+    let planets = GRAHA_KEYS.iter().zip(points).map(|(&key, (lon, speed))| position(key, lon, speed)).collect();
+
+    Ok(VedicChart {
+        sid_mode,
+        ayanamsa,
+        ascendant: position("ascendant", lagna, 0.0),
+        planets,
     })
 }
 
@@ -620,7 +1058,8 @@ pub fn is_void_of_course_moon(jd: f64) -> Result<bool> {
     let major_aspects = [0.0, 60.0, 90.0, 120.0, 180.0];
     let orb = 8.0;
 
-    for planet in &positions {
+    // Traditional planets only, the minor bodies do not end a void-of-course Moon
+    for planet in positions.iter().filter(|p| PLANET_KEYS.contains(&p.planet_key)) {
         if planet.planet_key == "moon" {
             continue;
         }
@@ -644,6 +1083,9 @@ pub fn is_void_of_course_moon(jd: f64) -> Result<bool> {
 }
 
 /// Get which house a planet occupies
+///
+/// Compares the ecliptic longitude with the cusps; see [`crate::house_pos`]
+/// for bodies with latitude and for the position within the house.
 pub fn get_planet_in_house(longitude: f64, house_cusps: &[HouseCusp]) -> u8 {
     for i in 0..12 {
         let current = &house_cusps[i];
@@ -722,6 +1164,151 @@ mod tests {
         assert!(!is_eclipse(jd).unwrap());
     }
 
+    #[test]
+    fn test_natal_chart_node_type() {
+        let jd = julian::julday(2024, 6, 21, 12.0, 1);
+        let true_chart = get_natal_chart(jd, 47.38, 8.54).unwrap();
+        let mean_chart = get_natal_chart_with_node(jd, 47.38, 8.54, NodeType::Mean).unwrap();
+        assert_eq!(true_chart.node_type, NodeType::True);
+        assert_eq!(mean_chart.node_type, NodeType::Mean);
+
+        // The true node oscillates around the mean node by less than 2°
+        let diff = angular_distance(true_chart.north_node_longitude, mean_chart.north_node_longitude);
+        assert!(diff > 0.0 && diff < 2.0, "diff {:.3}°", diff);
+    }
+
+    #[test]
+    fn test_natal_chart_sidereal() {
+        let jd = julian::julday(2024, 6, 21, 12.0, 1);
+        let tropical = get_natal_chart(jd, 47.38, 8.54).unwrap();
+        assert!(tropical.ayanamsa.is_none());
+
+        crate::set_sid_mode(Some(crate::SiderealMode::Lahiri));
+        let sidereal = get_natal_chart(jd, 47.38, 8.54).unwrap();
+        crate::set_sid_mode(None);
+        let ayanamsa = sidereal.ayanamsa.unwrap();
+        assert!((ayanamsa - 24.2).abs() < 0.05, "{}", ayanamsa);
+
+        // The Sun of the solstice stands in sidereal Gemini
+        assert_eq!(tropical.planets[0].sign_key, "cancer");
+        assert_eq!(sidereal.planets[0].sign_key, "gemini");
+        for (s, t) in sidereal.planets.iter().zip(&tropical.planets) {
+            let shift = angular_distance(t.longitude, s.longitude);
+            assert!((shift - ayanamsa).abs() < 0.01, "{}: {}", s.planet_key, shift);
+        }
+        assert!((angular_distance(tropical.ascendant, sidereal.ascendant) - ayanamsa).abs() < 1e-9);
+        let node_shift = angular_distance(tropical.north_node_longitude, sidereal.north_node_longitude);
+        assert!((node_shift - ayanamsa).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_nakshatras() {
+        assert_eq!(get_nakshatra_from_longitude(0.0), "ashwini");
+        assert_eq!(get_nakshatra_pada(0.0), 1);
+        assert_eq!(get_nakshatra_lord(0.0), "ketu");
+        // Rohini spans 10°00' to 23°20' Taurus, ruled by the Moon
+        assert_eq!(get_nakshatra_from_longitude(46.0), "rohini");
+        assert_eq!(get_nakshatra_pada(46.0), 2);
+        assert_eq!(get_nakshatra_lord(46.0), "moon");
+        assert_eq!(get_nakshatra_from_longitude(359.99), "revati");
+        assert_eq!(get_nakshatra_pada(359.99), 4);
+        assert_eq!(get_nakshatra_lord(359.99), "mercury");
+        assert_eq!(get_nakshatra_from_longitude(-0.01), "revati");
+    }
+
+    #[test]
+    fn test_vargas() {
+        for &(lon, varga, sign) in &[
+            (12.0, Varga::Rasi, "aries"),
+            (10.0, Varga::Hora, "leo"),
+            (20.0, Varga::Hora, "cancer"),
+            (40.0, Varga::Hora, "cancer"),
+            (15.0, Varga::Drekkana, "leo"),
+            (25.0, Varga::Drekkana, "sagittarius"),
+            (10.0, Varga::Chaturthamsa, "cancer"),
+            (33.0, Varga::Saptamsa, "scorpio"),
+            (2.0, Varga::Navamsa, "aries"),
+            (4.0, Varga::Navamsa, "taurus"),
+            (31.0, Varga::Navamsa, "capricorn"),
+            (61.0, Varga::Navamsa, "libra"),
+            (35.0, Varga::Dashamsa, "aquarius"),
+            (29.0, Varga::Dwadashamsa, "pisces"),
+            (31.0, Varga::Shodashamsa, "leo"),
+            (31.0, Varga::Vimshamsa, "sagittarius"),
+            (1.0, Varga::Chaturvimshamsa, "leo"),
+            (121.0, Varga::Bhamsa, "aries"),
+            (7.0, Varga::Trimshamsa, "aquarius"),
+            (37.0, Varga::Trimshamsa, "virgo"),
+            (59.0, Varga::Trimshamsa, "scorpio"),
+            (30.5, Varga::Khavedamsa, "libra"),
+            (90.5, Varga::Akshavedamsa, "aries"),
+            (60.5, Varga::Akshavedamsa, "sagittarius"),
+            (29.9, Varga::Shashtiamsa, "pisces"),
+        ] {
+            assert_eq!(get_varga_sign(lon, varga), sign, "{} {:?}", lon, varga);
+        }
+        // The degree spreads the part over the sign
+        assert!((get_varga_longitude(35.0, Varga::Navamsa) - 315.0).abs() < 1e-9);
+        assert!((get_varga_longitude(7.5, Varga::Trimshamsa) - 315.0).abs() < 1e-9);
+        assert_eq!(Varga::from_division(9), Some(Varga::Navamsa));
+        assert_eq!(Varga::from_division(5), None);
+    }
+
+    #[test]
+    fn test_vedic_chart() {
+        // Shortly after Mesha Sankranti 2024, at Delhi
+        let jd = julian::julday(2024, 4, 13, 17.0, 1);
+        let chart = get_vedic_chart(jd, 28.61, 77.21).unwrap();
+        assert_eq!(chart.sid_mode, SiderealMode::Lahiri);
+        let sun = &chart.planets[0];
+        assert_eq!((sun.sign_key, sun.nakshatra_key, sun.nakshatra_pada), ("aries", "ashwini", 1));
+        assert_eq!(sun.vargas[5], (Varga::Navamsa, "aries"));
+        assert_eq!(chart.ascendant.house, 1);
+
+        let (rahu, ketu) = (&chart.planets[7], &chart.planets[8]);
+        assert_eq!((rahu.planet_key, ketu.planet_key), ("rahu", "ketu"));
+        assert!((angular_distance(rahu.longitude, ketu.longitude) - 180.0).abs() < 1e-9);
+        assert!(rahu.is_retrograde && ketu.is_retrograde);
+        assert_eq!((rahu.house + 5) % 12 + 1, ketu.house);
+
+        // Other ayanamsas follow set_sid_mode
+        crate::set_sid_mode(Some(SiderealMode::Raman));
+        let raman = get_vedic_chart(jd, 28.61, 77.21).unwrap();
+        crate::set_sid_mode(None);
+        let shift = angular_distance(sun.longitude, raman.planets[0].longitude);
+        assert!((shift - (chart.ayanamsa - raman.ayanamsa)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_natal_chart_lilith() {
+        let jd = julian::julday(2024, 6, 21, 12.0, 1);
+        let mean_chart = get_natal_chart(jd, 47.38, 8.54).unwrap();
+        let oscu_chart =
+            get_natal_chart_with_points(jd, 47.38, 8.54, NodeType::True, LilithType::Osculating).unwrap();
+        assert_eq!(mean_chart.lilith_type, LilithType::Mean);
+        assert_eq!(oscu_chart.lilith_type, LilithType::Osculating);
+
+        let mean = calc_ut(jd, Planet::MeanApogee, false).unwrap();
+        assert!((mean_chart.lilith_longitude - mean.longitude).abs() < 1e-12);
+        assert_eq!(mean_chart.lilith_sign, get_sign_from_longitude(mean.longitude));
+
+        // The osculating apogee swings up to about 30° around the mean one
+        let diff = angular_distance(mean_chart.lilith_longitude, oscu_chart.lilith_longitude);
+        assert!(diff < 35.0, "diff {:.3}°", diff);
+    }
+
+    #[test]
+    fn test_all_positions_minor_bodies() {
+        let positions = get_all_planetary_positions(julian::julday(2024, 6, 21, 12.0, 1)).unwrap();
+        let keys: Vec<_> = positions.iter().map(|p| p.planet_key).collect();
+        assert_eq!(keys[..10], PLANET_KEYS);
+        assert_eq!(keys[10..], MINOR_BODY_KEYS);
+
+        // Before 1900 the charts keep the planets and drop the minor bodies
+        let old = get_all_planetary_positions(julian::julday(1850, 6, 21, 12.0, 1)).unwrap();
+        assert_eq!(old.len(), 10);
+    }
+
     #[test]
     fn test_planetary_hour() {
         // Sunday hour 0 (midnight) = Sun (day ruler)
@@ -783,6 +1370,8 @@ mod tests {
         let pos1 = PlanetPosition {
             planet_key: "sun",
             longitude: 0.0,
+            latitude: 0.0,
+            distance: 1.0,
             sign_key: "aries",
             sign_degree: 0.0,
             is_retrograde: false,
@@ -791,6 +1380,8 @@ mod tests {
         let pos2 = PlanetPosition {
             planet_key: "moon",
             longitude: 95.0, // 5° past exact square
+            latitude: 0.0,
+            distance: 0.0026,
             sign_key: "cancer",
             sign_degree: 5.0,
             is_retrograde: false,
@@ -29,6 +29,9 @@ pub const AU_KM: f64 = 149597870.7;
 /// Earth-Moon mass ratio
 pub const EARTH_MOON_MRAT: f64 = 81.30056907419062;
 
+/// Geocentric gravitational constant GM of the Earth (m^3/s^2)
+pub const GEOGCONST: f64 = 3.98600448e14;
+
 /// Mean obliquity of ecliptic at J2000 (degrees)
 pub const OBLIQUITY_J2000: f64 = 23.439291111;
 
@@ -61,12 +64,48 @@ pub const SE_NEPTUNE: i32 = 8;
 pub const SE_PLUTO: i32 = 9;
 pub const SE_MEAN_NODE: i32 = 10;
 pub const SE_TRUE_NODE: i32 = 11;
+pub const SE_MEAN_APOG: i32 = 12;
+pub const SE_OSCU_APOG: i32 = 13;
+pub const SE_EARTH: i32 = 14;
+pub const SE_CHIRON: i32 = 15;
+pub const SE_PHOLUS: i32 = 16;
+pub const SE_CERES: i32 = 17;
+pub const SE_PALLAS: i32 = 18;
+pub const SE_JUNO: i32 = 19;
+pub const SE_VESTA: i32 = 20;
+
+/// Numbers of bodies registered with `register_orbit`: `SE_FICT_OFFSET + n`
+pub const SE_FICT_OFFSET: i32 = 40;
+pub const SE_FICT_MAX: i32 = 999;
+
+/// Methods for `nod_aps`: mean or osculating orbit
+pub const SE_NODBIT_MEAN: i32 = 1;
+pub const SE_NODBIT_OSCU: i32 = 2;
 
 /// Calendar flag: Gregorian
 pub const SE_GREG_CAL: i32 = 1;
 
+/// `swe_set_delta_t_userdef` value that restores the built-in delta-T
+pub const SE_DELTAT_AUTOMATIC: f64 = -1e-10;
+
 /// Calculation flags
+pub const SEFLG_TRUEPOS: i32 = 16;
+pub const SEFLG_J2000: i32 = 32;
+pub const SEFLG_NONUT: i32 = 64;
 pub const SEFLG_SPEED: i32 = 256;
+pub const SEFLG_NOABERR: i32 = 1024;
+pub const SEFLG_EQUATORIAL: i32 = 2048;
+pub const SEFLG_XYZ: i32 = 4096;
+pub const SEFLG_TOPOCTR: i32 = 32768;
+pub const SEFLG_SIDEREAL: i32 = 65536;
+
+/// Sidereal modes (ayanamsas) for `swe_set_sid_mode`
+pub const SE_SIDM_FAGAN_BRADLEY: i32 = 0;
+pub const SE_SIDM_LAHIRI: i32 = 1;
+pub const SE_SIDM_RAMAN: i32 = 3;
+pub const SE_SIDM_KRISHNAMURTI: i32 = 5;
+pub const SE_SIDM_TRUE_CITRA: i32 = 27;
+pub const SE_SIDM_USER: i32 = 255;
 
 /// Timescale for Moshier planets
 pub const MOSHIER_TIMESCALE: f64 = 3652500.0;
@@ -0,0 +1,175 @@
+//! Calculation settings shared by the position functions
+//!
+//! Like the `swe_set_*` calls of Swiss Ephemeris, settings stay in effect for
+//! every later calculation until changed. They are kept per thread, so
+//! independent threads (and test cases) never see each other's settings.
+//!
+//! Orbits registered with [`register_orbit`] are the exception: they are
+//! shared by the whole process, so a [`Planet::Custom`] handle works on any
+//! thread. Web workers run separate WASM instances and register their own.
+
+use std::cell::Cell;
+use std::sync::{PoisonError, RwLock};
+
+use crate::constants::*;
+use crate::{Error, KeplerElements, Observer, Planet, Precision, Result, SiderealMode};
+
+thread_local! {
+    static PRECISION: Cell<Precision> = const { Cell::new(Precision::Standard) };
+    static TOPO: Cell<Option<Observer>> = const { Cell::new(None) };
+    static DELTA_T_USERDEF: Cell<Option<f64>> = const { Cell::new(None) };
+    static SID_MODE: Cell<Option<SiderealMode>> = const { Cell::new(None) };
+}
+
+/// Bodies registered with [`register_orbit`], indexed by [`Planet::Custom`]
+static ORBITS: RwLock<Vec<(String, KeplerElements)>> = RwLock::new(Vec::new());
+
+/// Set the series precision used by planet and Moon calculations
+pub fn set_precision(precision: Precision) {
+    PRECISION.with(|p| p.set(precision));
+}
+
+/// Get the series precision currently in effect
+pub fn get_precision() -> Precision {
+    PRECISION.with(|p| p.get())
+}
+
+/// Set the observer for topocentric positions (`SEFLG_TOPOCTR`)
+///
+/// Argument order follows `swe_set_topo`: geographic longitude and latitude
+/// in degrees, altitude above the WGS84 ellipsoid in meters.
+pub fn set_topo(longitude: f64, latitude: f64, altitude: f64) {
+    TOPO.with(|t| t.set(Some(Observer { latitude, longitude, altitude })));
+}
+
+/// Get the observer set with [`set_topo`], if any
+pub fn get_topo() -> Option<Observer> {
+    TOPO.with(|t| t.get())
+}
+
+/// Fix delta-T to a value in days, or return to the built-in model with `None`
+///
+/// Like `swe_set_delta_t_userdef`, this pins every UT conversion, which keeps
+/// regression tests reproducible when the delta-T model changes.
+pub fn set_delta_t_userdef(delta_t: Option<f64>) {
+    DELTA_T_USERDEF.with(|d| d.set(delta_t));
+}
+
+/// Get the delta-T set with [`set_delta_t_userdef`], if any
+pub fn get_delta_t_userdef() -> Option<f64> {
+    DELTA_T_USERDEF.with(|d| d.get())
+}
+
+/// Select the sidereal zodiac, or return to the tropical zodiac with `None`
+///
+/// With a mode set, [`crate::calc_ut`], [`crate::calc_houses`] and the
+/// charts of [`crate::astrology`] give sidereal longitudes. The flag-based
+/// functions keep the Swiss Ephemeris convention and are sidereal only with
+/// `SEFLG_SIDEREAL`, which uses this mode (Fagan/Bradley if none is set).
+pub fn set_sid_mode(mode: Option<SiderealMode>) {
+    SID_MODE.with(|m| m.set(mode));
+}
+
+/// Get the sidereal mode set with [`set_sid_mode`], `None` for the tropical zodiac
+pub fn get_sid_mode() -> Option<SiderealMode> {
+    SID_MODE.with(|m| m.get())
+}
+
+/// Register a body from its Keplerian elements
+///
+/// The returned [`Planet::Custom`] works with [`crate::calc_ut`] and the other
+/// position functions like any planet; its Swiss number is
+/// `SE_FICT_OFFSET + n`. Only elliptic orbits are supported.
+pub fn register_orbit(name: &str, elements: KeplerElements) -> Result<Planet> {
+    if !(elements.semi_major > 0.0 && (0.0..1.0).contains(&elements.eccentricity)) {
+        return Err(Error::CalculationError(format!("{}: orbit must be elliptic", name)));
+    }
+    // A panic cannot leave the list half-updated, so a poisoned lock is still usable
+    let mut orbits = ORBITS.write().unwrap_or_else(PoisonError::into_inner);
+    if orbits.len() > (SE_FICT_MAX - SE_FICT_OFFSET) as usize {
+        return Err(Error::CalculationError("too many registered orbits".to_string()));
+    }
+    orbits.push((name.to_string(), elements));
+    Ok(Planet::Custom((orbits.len() - 1) as u16))
+}
+
+/// Get the elements of a body registered with [`register_orbit`]
+pub fn get_orbit(index: u16) -> Option<KeplerElements> {
+    let orbits = ORBITS.read().unwrap_or_else(PoisonError::into_inner);
+    orbits.get(index as usize).map(|(_, el)| *el)
+}
+
+/// Get the name a body was registered with
+pub fn get_orbit_name(index: u16) -> Option<String> {
+    let orbits = ORBITS.read().unwrap_or_else(PoisonError::into_inner);
+    orbits.get(index as usize).map(|(name, _)| name.clone())
+}
+
+/// Forget all bodies registered with [`register_orbit`], on every thread
+pub fn clear_orbits() {
+    ORBITS.write().unwrap_or_else(PoisonError::into_inner).clear();
+}
+
+/// Serializes the tests that register orbits, which share one registry
+#[cfg(test)]
+pub(crate) static ORBITS_TEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_precision_roundtrip() {
+        assert_eq!(get_precision(), Precision::Standard);
+        set_precision(Precision::Fast);
+        assert_eq!(get_precision(), Precision::Fast);
+        set_precision(Precision::Standard);
+    }
+
+    #[test]
+    fn test_topo_roundtrip() {
+        assert!(get_topo().is_none());
+        set_topo(8.54, 47.38, 408.0);
+        let observer = get_topo().unwrap();
+        assert_eq!(observer.longitude, 8.54);
+        assert_eq!(observer.latitude, 47.38);
+        assert_eq!(observer.altitude, 408.0);
+    }
+
+    #[test]
+    fn test_delta_t_userdef_roundtrip() {
+        assert!(get_delta_t_userdef().is_none());
+        set_delta_t_userdef(Some(0.001));
+        assert_eq!(get_delta_t_userdef(), Some(0.001));
+        set_delta_t_userdef(None);
+    }
+
+    #[test]
+    fn test_sid_mode_roundtrip() {
+        assert!(get_sid_mode().is_none());
+        set_sid_mode(Some(SiderealMode::Lahiri));
+        assert_eq!(get_sid_mode(), Some(SiderealMode::Lahiri));
+        set_sid_mode(None);
+    }
+
+    #[test]
+    fn test_orbit_registry() {
+        let _lock = ORBITS_TEST_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        clear_orbits();
+        let elements = KeplerElements { semi_major: 67.8, eccentricity: 0.44, ..Default::default() };
+        assert_eq!(register_orbit("Eris", elements).unwrap(), Planet::Custom(0));
+        assert_eq!(get_orbit(0).unwrap().semi_major, 67.8);
+        assert_eq!(get_orbit_name(0).as_deref(), Some("Eris"));
+
+        let hyperbolic = KeplerElements { semi_major: 1.0, eccentricity: 1.2, ..Default::default() };
+        assert!(register_orbit("comet", hyperbolic).is_err());
+
+        // Handles stay valid on other threads
+        let from_thread = std::thread::spawn(|| get_orbit_name(0)).join().unwrap();
+        assert_eq!(from_thread.as_deref(), Some("Eris"));
+
+        clear_orbits();
+        assert!(get_orbit(0).is_none());
+        assert!(get_delta_t_userdef().is_none());
+    }
+}
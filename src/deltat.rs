@@ -0,0 +1,225 @@
+//! Delta-T (TT - UT)
+//!
+//! Between 1620 and the end of the tables, delta-T is interpolated from
+//! observed values: every other year from Meeus table 10.A until 1954, and
+//! every year from the IERS/USNO values since 1955, when atomic time made the
+//! observations precise. Outside of them, the Espenak-Meeus (2006) polynomials
+//! are used. The difference between the polynomial and the first or last table
+//! value fades out over a century beyond each end, so there are no jumps.
+
+use crate::constants::*;
+
+/// First year of [`DELTA_T_BIENNIAL`]
+const BIENNIAL_START: f64 = 1620.0;
+/// First year of [`DELTA_T_YEARLY`]
+const YEARLY_START: f64 = 1955.0;
+/// Years over which the offset at either end of the tables fades out
+const TABLE_BLEND_YEARS: f64 = 100.0;
+
+/// Observed delta-T in seconds at the start of every second year, 1620-1954
+const DELTA_T_BIENNIAL: &[f64] = &[
+    // 1620
+    121.0, 112.0, 103.0, 95.0, 88.0, 82.0, 77.0, 72.0, 68.0, 63.0,
+    // 1640
+    60.0, 56.0, 53.0, 51.0, 48.0, 46.0, 44.0, 42.0, 40.0, 38.0,
+    // 1660
+    35.0, 33.0, 31.0, 29.0, 26.0, 24.0, 22.0, 20.0, 18.0, 16.0,
+    // 1680
+    14.0, 12.0, 11.0, 10.0, 9.0, 8.0, 7.0, 7.0, 7.0, 7.0,
+    // 1700
+    7.0, 7.0, 8.0, 8.0, 9.0, 9.0, 9.0, 9.0, 9.0, 10.0,
+    // 1720
+    10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 11.0, 11.0, 11.0,
+    // 1740
+    11.0, 11.0, 12.0, 12.0, 12.0, 12.0, 13.0, 13.0, 13.0, 14.0,
+    // 1760
+    14.0, 14.0, 14.0, 15.0, 15.0, 15.0, 15.0, 15.0, 16.0, 16.0,
+    // 1780
+    16.0, 16.0, 16.0, 16.0, 16.0, 16.0, 15.0, 15.0, 14.0, 13.0,
+    // 1800
+    13.1, 12.5, 12.2, 12.0, 12.0, 12.0, 12.0, 12.0, 12.0, 11.9,
+    // 1820
+    11.6, 11.0, 10.2, 9.2, 8.2, 7.1, 6.2, 5.6, 5.4, 5.3,
+    // 1840
+    5.4, 5.6, 5.9, 6.2, 6.5, 6.8, 7.1, 7.3, 7.5, 7.6,
+    // 1860
+    7.7, 7.3, 6.2, 5.2, 2.7, 1.4, -1.2, -2.8, -3.8, -4.8,
+    // 1880
+    -5.5, -5.3, -5.6, -5.7, -5.9, -6.0, -6.3, -6.5, -6.2, -4.7,
+    // 1900
+    -2.8, -0.1, 2.6, 5.3, 7.7, 10.4, 13.3, 16.0, 18.2, 20.2,
+    // 1920
+    21.1, 22.4, 23.5, 23.8, 24.3, 24.0, 23.9, 23.9, 23.7, 24.0,
+    // 1940
+    24.3, 25.3, 26.2, 27.3, 28.2, 29.1, 30.0, 30.7,
+];
+
+/// Observed delta-T in seconds at the start of every year, 1955-2026
+const DELTA_T_YEARLY: &[f64] = &[
+    // 1955
+    31.07, 31.35, 31.68, 32.18, 32.68,
+    // 1960
+    33.15, 33.59, 34.00, 34.47, 35.03, 35.73, 36.54, 37.43, 38.29, 39.20,
+    // 1970
+    40.18, 41.17, 42.23, 43.37, 44.49, 45.48, 46.46, 47.52, 48.53, 49.59,
+    // 1980
+    50.54, 51.38, 52.17, 52.96, 53.79, 54.34, 54.87, 55.32, 55.82, 56.30,
+    // 1990
+    56.86, 57.57, 58.31, 59.12, 59.98, 60.78, 61.63, 62.29, 62.97, 63.47,
+    // 2000
+    63.83, 64.09, 64.30, 64.47, 64.57, 64.69, 64.85, 65.15, 65.46, 65.78,
+    // 2010
+    66.07, 66.32, 66.60, 66.91, 67.28, 67.64, 68.10, 68.59, 68.97, 69.22,
+    // 2020
+    69.36, 69.36, 69.29, 69.20, 69.17, 69.14, 69.10,
+];
+
+/// Delta-T in days for a Julian day, from the tables or the polynomials
+pub fn delta_t_auto(jd: f64) -> f64 {
+    let year = 2000.0 + (jd - J2000) / 365.25;
+    delta_t_seconds(year) / 86400.0
+}
+
+/// Last year of [`DELTA_T_YEARLY`]
+fn table_end() -> f64 {
+    YEARLY_START + (DELTA_T_YEARLY.len() - 1) as f64
+}
+
+/// Delta-T in seconds for a decimal year
+fn delta_t_seconds(year: f64) -> f64 {
+    let (edge, edge_value) = if year < BIENNIAL_START {
+        (BIENNIAL_START, DELTA_T_BIENNIAL[0])
+    } else if year > table_end() {
+        (table_end(), DELTA_T_YEARLY[DELTA_T_YEARLY.len() - 1])
+    } else {
+        return table_value(year);
+    };
+
+    let offset = edge_value - espenak_meeus(edge);
+    let fade = (1.0 - (year - edge).abs() / TABLE_BLEND_YEARS).max(0.0);
+    espenak_meeus(year) + offset * fade
+}
+
+/// Delta-T in seconds interpolated from the tables
+///
+/// The year between the last biennial and the first yearly value is bridged
+/// linearly as well.
+fn table_value(year: f64) -> f64 {
+    let biennial_end = BIENNIAL_START + 2.0 * (DELTA_T_BIENNIAL.len() - 1) as f64;
+    if year >= YEARLY_START {
+        interpolate(DELTA_T_YEARLY, YEARLY_START, 1.0, year)
+    } else if year >= biennial_end {
+        let (a, b) = (DELTA_T_BIENNIAL[DELTA_T_BIENNIAL.len() - 1], DELTA_T_YEARLY[0]);
+        a + (b - a) * (year - biennial_end) / (YEARLY_START - biennial_end)
+    } else {
+        interpolate(DELTA_T_BIENNIAL, BIENNIAL_START, 2.0, year)
+    }
+}
+
+/// Linear interpolation in a table of values `step` years apart from `start`
+fn interpolate(table: &[f64], start: f64, step: f64, year: f64) -> f64 {
+    let pos = (year - start) / step;
+    let i = (pos as usize).min(table.len() - 2);
+    let frac = pos - i as f64;
+    table[i] + (table[i + 1] - table[i]) * frac
+}
+
+/// Espenak-Meeus (2006) polynomial expressions for delta-T in seconds
+///
+/// From the NASA Five Millennium Canon of Solar Eclipses.
+fn espenak_meeus(year: f64) -> f64 {
+    let long_term = |y: f64| {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u * u
+    };
+
+    if year < -500.0 {
+        long_term(year)
+    } else if year < 500.0 {
+        let u = year / 100.0;
+        10583.6 - 1014.41 * u + 33.78311 * u.powi(2) - 5.952053 * u.powi(3)
+            - 0.1798452 * u.powi(4) + 0.022174192 * u.powi(5) + 0.0090316521 * u.powi(6)
+    } else if year < 1600.0 {
+        let u = (year - 1000.0) / 100.0;
+        1574.2 - 556.01 * u + 71.23472 * u.powi(2) + 0.319781 * u.powi(3)
+            - 0.8503463 * u.powi(4) - 0.005050998 * u.powi(5) + 0.0083572073 * u.powi(6)
+    } else if year < 1700.0 {
+        let t = year - 1600.0;
+        120.0 - 0.9808 * t - 0.01532 * t.powi(2) + t.powi(3) / 7129.0
+    } else if year < 1800.0 {
+        let t = year - 1700.0;
+        8.83 + 0.1603 * t - 0.0059285 * t.powi(2) + 0.00013336 * t.powi(3) - t.powi(4) / 1174000.0
+    } else if year < 1860.0 {
+        let t = year - 1800.0;
+        13.72 - 0.332447 * t + 0.0068612 * t.powi(2) + 0.0041116 * t.powi(3)
+            - 0.00037436 * t.powi(4) + 0.0000121272 * t.powi(5) - 0.0000001699 * t.powi(6)
+            + 0.000000000875 * t.powi(7)
+    } else if year < 1900.0 {
+        let t = year - 1860.0;
+        7.62 + 0.5737 * t - 0.251754 * t.powi(2) + 0.01680668 * t.powi(3)
+            - 0.0004473624 * t.powi(4) + t.powi(5) / 233174.0
+    } else if year < 1920.0 {
+        let t = year - 1900.0;
+        -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3) - 0.000197 * t.powi(4)
+    } else if year < 1941.0 {
+        let t = year - 1920.0;
+        21.20 + 0.84493 * t - 0.076100 * t.powi(2) + 0.0020936 * t.powi(3)
+    } else if year < 1961.0 {
+        let t = year - 1950.0;
+        29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+    } else if year < 1986.0 {
+        let t = year - 1975.0;
+        45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+    } else if year < 2005.0 {
+        let t = year - 2000.0;
+        63.86 + 0.3345 * t - 0.060374 * t.powi(2) + 0.0017275 * t.powi(3)
+            + 0.000651814 * t.powi(4) + 0.00002373599 * t.powi(5)
+    } else if year < 2050.0 {
+        let t = year - 2000.0;
+        62.92 + 0.32217 * t + 0.005589 * t.powi(2)
+    } else if year < 2150.0 {
+        long_term(year) - 0.5628 * (2150.0 - year)
+    } else {
+        long_term(year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_values() {
+        // Meeus example 10.a: 1977 February 18, delta-T = 48 s (table value 47.6 s)
+        assert!((delta_t_seconds(1977.13) - 47.6).abs() < 0.5);
+        assert!((delta_t_seconds(2000.0) - 63.83).abs() < 1e-9);
+        assert!((delta_t_seconds(1925.0) - 23.65).abs() < 1e-9);
+        // Yearly values: TT - UTC = 69.184 s less UT1 - UTC = +0.59 s after
+        // the leap second of 2017 January 1
+        assert!((delta_t_seconds(2017.0) - 68.59).abs() < 1e-9);
+        assert!((delta_t_seconds(1954.5) - 30.885).abs() < 1e-9);
+        assert_eq!(table_end(), 2026.0);
+    }
+
+    #[test]
+    fn test_polynomials_continuous() {
+        // The Espenak-Meeus segments join within a second or so
+        for &seam in &[-500.0, 500.0, 1600.0, 1700.0, 1800.0, 1860.0, 1900.0, 1920.0,
+                       1941.0, 1961.0, 1986.0, 2005.0, 2050.0, 2150.0] {
+            let jump = espenak_meeus(seam) - espenak_meeus(seam - 1e-6);
+            assert!(jump.abs() < 1.5, "jump of {:.2}s at {}", jump, seam);
+        }
+    }
+
+    #[test]
+    fn test_table_seams() {
+        // No jumps where the table hands over to the polynomials
+        for &seam in &[BIENNIAL_START, table_end()] {
+            let jump = delta_t_seconds(seam + 1e-6) - delta_t_seconds(seam - 1e-6);
+            assert!(jump.abs() < 1.0, "jump of {:.2}s at {}", jump, seam);
+        }
+        // Long-range values (Espenak-Meeus): about 17190 s at -500 and 1570 s at 1000
+        assert!((delta_t_seconds(-500.0) - 17190.0).abs() < 20.0);
+        assert!((delta_t_seconds(1000.0) - 1574.2).abs() < 1e-9);
+    }
+}
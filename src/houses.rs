@@ -1,22 +1,56 @@
 //! House cusp calculations
 //!
-//! Implements Placidus house system.
+//! Implements the Placidus, Koch, Equal (from the Ascendant or the MC), Whole
+//! Sign, Porphyry, Regiomontanus, Campanus, Alcabitius, Morinus, Topocentric
+//! and Meridian house systems and the 36 Gauquelin sectors, following the
+//! formulas of Swiss Ephemeris (`swehouse.c`), and the house position of a
+//! body in each of them.
 
 use crate::constants::*;
 use crate::math::*;
-use crate::{delta_t, Houses, Result};
+use crate::{calc_ut_flags, context, delta_t, sidereal, Error, HouseSystem, Houses, Planet, Result};
 
-/// Calculate Placidus house cusps
-pub fn calc_houses_placidus(jd_ut: f64, lat: f64, lon: f64) -> Result<Houses> {
+/// Calculate house cusps in the given system
+pub fn calc_houses(jd_ut: f64, lat: f64, lon: f64, hsys: HouseSystem) -> Result<Houses> {
     // Convert to ephemeris time
     let jd_et = jd_ut + delta_t(jd_ut);
 
-    // Obliquity of ecliptic
-    let eps = obliquity(jd_et);
+    // ARMC (local sidereal time in degrees) and obliquity of ecliptic
+    let mut houses = houses_armc(armc(jd_ut, lon), lat, obliquity(jd_et) * RAD_TO_DEG, hsys)?;
+
+    // Cusps and angles are referred to the mean equinox, as is the ayanamsa
+    if let Some(mode) = context::get_sid_mode() {
+        let ayanamsa = sidereal::ayanamsa(jd_et, mode)?;
+        let to_sidereal = |lon: &mut f64| *lon = deg_norm(*lon - ayanamsa);
+        houses.cusps[1..].iter_mut().chain(houses.sectors.iter_mut().skip(1)).for_each(to_sidereal);
+        for angle in [
+            &mut houses.ascendant,
+            &mut houses.mc,
+            &mut houses.vertex,
+            &mut houses.equatorial_ascendant,
+            &mut houses.co_ascendant_koch,
+            &mut houses.co_ascendant_munkasey,
+            &mut houses.polar_ascendant,
+        ] {
+            to_sidereal(angle);
+        }
+        // Whole signs are counted from the sign of the sidereal Ascendant
+        if houses.system == HouseSystem::WholeSign {
+            houses.cusps = equal_cusps((houses.ascendant / 30.0).floor() * 30.0, 1);
+        }
+    }
+    Ok(houses)
+}
 
-    // ARMC (local sidereal time in degrees)
-    let armc_deg = armc(jd_ut, lon);
+/// Calculate house cusps from the ARMC, geographic latitude and obliquity
+/// (all in degrees)
+pub fn houses_armc(armc_deg: f64, lat: f64, eps_deg: f64, hsys: HouseSystem) -> Result<Houses> {
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(Error::CalculationError(format!("latitude {} out of range", lat)));
+    }
+    let armc_deg = deg_norm(armc_deg);
     let armc_rad = armc_deg * DEG_TO_RAD;
+    let eps = eps_deg * DEG_TO_RAD;
 
     // Latitude in radians
     let lat_rad = lat * DEG_TO_RAD;
@@ -25,23 +59,180 @@ pub fn calc_houses_placidus(jd_ut: f64, lat: f64, lon: f64) -> Result<Houses> {
     let mc = calc_mc(armc_rad, eps);
 
     // Calculate Ascendant
-    let asc = calc_ascendant(armc_rad, lat_rad, eps);
+    let mut asc = calc_ascendant(armc_rad, lat_rad, eps);
+
+    // Within the polar circle the rising point of the ecliptic can lie west of
+    // the MC; like Swiss Ephemeris, take the opposite point so that the
+    // quadrants keep their order
+    let polar = lat.abs() >= 90.0 - eps * RAD_TO_DEG;
+    if polar && deg_norm((asc - mc) * RAD_TO_DEG) > 180.0 {
+        asc = (asc + std::f64::consts::PI) % TWOPI;
+    }
+
+    // Time-based systems break down where parts of the ecliptic are circumpolar
+    let mut system = if polar && !hsys.is_defined_in_polar_circle() {
+        HouseSystem::Porphyry
+    } else {
+        hsys
+    };
+
+    // Calculate intermediate cusps
+    let mut cusps = calc_cusps(system, armc_deg, lat, eps, mc * RAD_TO_DEG, asc * RAD_TO_DEG);
 
-    // Calculate intermediate cusps using Placidus method
-    let cusps = calc_placidus_cusps(armc_deg, lat_rad, eps, mc, asc);
+    // The house circles of the other quadrant systems can meet the ecliptic in
+    // the opposite order there, which would make the houses run clockwise
+    if polar && !cusps_in_order(&cusps) {
+        system = HouseSystem::Porphyry;
+        cusps = calc_cusps(system, armc_deg, lat, eps, mc * RAD_TO_DEG, asc * RAD_TO_DEG);
+    }
+    let sectors = if system == HouseSystem::Gauquelin {
+        calc_gauquelin_sectors(armc_deg, lat, eps, mc * RAD_TO_DEG, asc * RAD_TO_DEG)
+    } else {
+        Vec::new()
+    };
 
     // Calculate Vertex
     let vertex = calc_vertex(armc_rad, lat_rad, eps);
 
+    // Further angles, as in the Swiss Ephemeris ascmc[] array
+    let (sin_eps, cos_eps) = eps.sin_cos();
+    let polar_ascendant = asc1_deg(armc_deg - 90.0, lat, sin_eps, cos_eps);
+    let munkasey_pole = if lat >= 0.0 { 90.0 - lat } else { -90.0 - lat };
+
     Ok(Houses {
         cusps,
+        sectors,
         ascendant: deg_norm(asc * RAD_TO_DEG),
         mc: deg_norm(mc * RAD_TO_DEG),
         armc: armc_deg,
         vertex: deg_norm(vertex * RAD_TO_DEG),
+        equatorial_ascendant: asc1_deg(armc_deg + 90.0, 0.0, sin_eps, cos_eps),
+        co_ascendant_koch: deg_norm(polar_ascendant + 180.0),
+        co_ascendant_munkasey: asc1_deg(armc_deg + 90.0, munkasey_pole, sin_eps, cos_eps),
+        polar_ascendant,
+        system,
     })
 }
 
+/// House position of a body with ecliptic longitude `lon` and latitude
+/// `lat_body` (Swiss Ephemeris `swe_house_pos`)
+///
+/// Returns a value in [1, 13): 7.35 is 35% of the way through the seventh
+/// house, or the Gauquelin sector in [1, 37). All arguments are in degrees.
+///
+/// Gauquelin sectors depend on the semi-arc of the body alone and are not
+/// replaced by Porphyry houses within the polar circles.
+pub fn house_pos(armc: f64, lat: f64, eps: f64, hsys: HouseSystem, lon: f64, lat_body: f64) -> Result<f64> {
+    let houses = houses_armc(armc, lat, eps, hsys)?;
+    let system = if hsys == HouseSystem::Gauquelin { hsys } else { houses.system };
+    let th = houses.armc;
+    let eps = eps * DEG_TO_RAD;
+
+    // Right ascension and declination of the body
+    let eq = cart_to_pol(&coord_trans(&pol_to_cart(&[lon * DEG_TO_RAD, lat_body * DEG_TO_RAD, 1.0]), -eps));
+    let (ra, dec) = (eq[0] * RAD_TO_DEG, eq[1]);
+
+    let pos = match system {
+        HouseSystem::Equal => deg_norm(lon - houses.ascendant) / 30.0 + 1.0,
+        HouseSystem::EqualMc | HouseSystem::WholeSign => deg_norm(lon - houses.cusps[1]) / 30.0 + 1.0,
+        HouseSystem::Porphyry | HouseSystem::Koch | HouseSystem::Topocentric => {
+            between_cusps(&houses.cusps, deg_norm(lon))
+        }
+        // House circles are hour circles at equal steps of right ascension
+        HouseSystem::Meridian => deg_norm(ra - th - 90.0) / 30.0 + 1.0,
+        HouseSystem::Morinus => {
+            // Right ascension of the equator point on the body's circle of longitude
+            let (sin_lon, cos_lon) = (lon * DEG_TO_RAD).sin_cos();
+            let ra = sin_lon.atan2(cos_lon * eps.cos()) * RAD_TO_DEG;
+            deg_norm(ra - th - 90.0) / 30.0 + 1.0
+        }
+        HouseSystem::Placidus | HouseSystem::Gauquelin => {
+            // Fraction of the diurnal or nocturnal semi-arc from the meridian.
+            // A body that never sets has a diurnal semi-arc of 180°, one that
+            // never rises a diurnal semi-arc of 0°
+            let ad_arg = (lat * DEG_TO_RAD).tan() * dec.tan();
+            let sda = 90.0 + ad_arg.clamp(-1.0, 1.0).asin() * RAD_TO_DEG;
+            let ha = deg_norm_180(th - ra);
+            let pos = if ha.abs() <= sda && sda > 0.0 {
+                10.0 - 3.0 * ha / sda
+            } else {
+                4.0 - 3.0 * deg_norm_180(ha - 180.0) / (180.0 - sda)
+            };
+            if system == HouseSystem::Gauquelin {
+                // Three sectors to a house, numbered clockwise from the Ascendant
+                let sector = 40.0 - 3.0 * pos;
+                return Ok(if sector >= 37.0 { sector - 36.0 } else { sector });
+            }
+            pos
+        }
+        HouseSystem::Alcabitius => {
+            // Hour circles trisecting the semi-arcs of the Ascendant
+            let asc_dec = ((houses.ascendant * DEG_TO_RAD).sin() * eps.sin()).asin();
+            let sda = (-(lat * DEG_TO_RAD).tan() * asc_dec.tan()).clamp(-1.0, 1.0).acos() * RAD_TO_DEG;
+            let sna = 180.0 - sda;
+            let d = deg_norm(ra - th);
+            if d < sda {
+                10.0 + 3.0 * d / sda
+            } else if d < 180.0 {
+                1.0 + 3.0 * (d - sda) / sna
+            } else if d < 180.0 + sda {
+                4.0 + 3.0 * (d - 180.0) / sda
+            } else {
+                7.0 + 3.0 * (d - 180.0 - sda) / sna
+            }
+        }
+        HouseSystem::Regiomontanus | HouseSystem::Campanus => {
+            // Position in the horizon frame: towards the south point, the
+            // east point and the zenith
+            let ha = (th - ra) * DEG_TO_RAD;
+            let (sin_lat, cos_lat) = (lat * DEG_TO_RAD).sin_cos();
+            let m = [dec.cos() * ha.cos(), dec.cos() * ha.sin(), dec.sin()];
+            let east = -m[1];
+            let up = cos_lat * m[0] + sin_lat * m[2];
+            // Both systems' house circles pass through the north and south points
+            let angle = if system == HouseSystem::Campanus {
+                // Measured along the prime vertical
+                up.atan2(east)
+            } else {
+                // Measured along the equator: the hour angle where the circle
+                // through the body crosses it
+                -east.atan2(up / cos_lat) + std::f64::consts::FRAC_PI_2
+            };
+            deg_norm(-angle * RAD_TO_DEG) / 30.0 + 1.0
+        }
+    };
+
+    Ok(if pos >= 13.0 { pos - 12.0 } else { pos })
+}
+
+/// Gauquelin sector of a body at a moment and place (degrees)
+pub fn gauquelin_sector(jd_ut: f64, planet: Planet, lat: f64, lon: f64, with_latitude: bool) -> Result<f64> {
+    let jd_et = jd_ut + delta_t(jd_ut);
+    // Tropical, the house circles are fixed to the equinox
+    let pos = calc_ut_flags(jd_ut, planet, 0)?;
+    let lat_body = if with_latitude { pos.latitude } else { 0.0 };
+    let eps = obliquity(jd_et) * RAD_TO_DEG;
+    house_pos(armc(jd_ut, lon), lat, eps, HouseSystem::Gauquelin, pos.longitude, lat_body)
+}
+
+/// Do the cusps follow each other in the order of the signs?
+fn cusps_in_order(cusps: &[f64; 13]) -> bool {
+    let total: f64 = (1..=12).map(|i| deg_norm(cusps[i % 12 + 1] - cusps[i])).sum();
+    (total - 360.0).abs() < 1e-6
+}
+
+/// Fractional house of an ecliptic longitude from the cusps
+fn between_cusps(cusps: &[f64; 13], lon: f64) -> f64 {
+    for i in 1..=12 {
+        let width = deg_norm(cusps[i % 12 + 1] - cusps[i]);
+        let offset = deg_norm(lon - cusps[i]);
+        if offset < width {
+            return i as f64 + offset / width;
+        }
+    }
+    1.0
+}
+
 /// Calculate MC (Medium Coeli / Midheaven)
 fn calc_mc(armc: f64, eps: f64) -> f64 {
     let (sin_armc, cos_armc) = armc.sin_cos();
@@ -156,6 +347,40 @@ fn calc_placidus_cusps(armc_deg: f64, lat: f64, eps: f64, mc: f64, asc: f64) ->
     cusps
 }
 
+/// Gauquelin sector cusps (degrees, index 1-36)
+///
+/// Each quadrant of the semi-arcs is divided in nine with the Placidus
+/// iteration; sector cusps 1, 10, 19 and 28 are the Ascendant, MC, Descendant
+/// and IC, and every third cusp is a Placidus cusp.
+fn calc_gauquelin_sectors(th: f64, lat: f64, eps: f64, mc: f64, asc: f64) -> Vec<f64> {
+    let (sin_eps, cos_eps) = eps.sin_cos();
+    let tan_lat = (lat * DEG_TO_RAD).tan();
+    let tan_eps = eps.tan();
+    // Ascensional difference at the obliquity circle, for the seed pole heights
+    let a = (tan_lat * tan_eps).clamp(-1.0, 1.0).asin();
+
+    let mut sectors = vec![0.0; 37];
+    sectors[1] = asc;
+    sectors[10] = mc;
+    for k in 1..9 {
+        // Fraction of the semi-arc from the meridian
+        let p = k as f64 / 9.0;
+        let seed = if tan_eps.abs() > 1e-15 { ((a * p).sin() / tan_eps).atan() * RAD_TO_DEG } else { 0.0 };
+        // Eastern halves of the diurnal and nocturnal arcs, from the MC and
+        // the IC towards the Ascendant
+        sectors[10 - k] = placidus_cusp_deg(deg_norm(th + 90.0 * p), tan_lat, sin_eps, cos_eps, 1.0 / p, seed);
+        sectors[28 + k] = placidus_cusp_deg(deg_norm(th + 180.0 - 90.0 * p), tan_lat, sin_eps, cos_eps, 1.0 / p, seed);
+    }
+    // The western halves are opposite
+    for g in 1..=10 {
+        sectors[g + 18] = deg_norm(sectors[g] + 180.0);
+    }
+    for g in 29..=36 {
+        sectors[g - 18] = deg_norm(sectors[g] + 180.0);
+    }
+    sectors
+}
+
 /// Compute a single Placidus intermediate cusp (in degrees).
 ///
 /// Implements the Swiss Ephemeris pole-height iteration: at each step,
@@ -221,6 +446,139 @@ fn placidus_cusp_deg(
     cusp
 }
 
+/// Calculate the house cusps of a system (degrees)
+///
+/// `th` is the ARMC, `lat` the geographic latitude, `mc` and `asc` the
+/// angles, all in degrees; `eps` is the obliquity in radians.
+fn calc_cusps(hsys: HouseSystem, th: f64, lat: f64, eps: f64, mc: f64, asc: f64) -> [f64; 13] {
+    let (sin_eps, cos_eps) = eps.sin_cos();
+    let lat_rad = lat * DEG_TO_RAD;
+    let tan_lat = lat_rad.tan();
+    // Longitude of the ecliptic point with right ascension `ra` and pole height `f`
+    let cusp_at = |ra: f64, f: f64| asc1_deg(ra, f, sin_eps, cos_eps);
+
+    match hsys {
+        HouseSystem::Placidus | HouseSystem::Gauquelin => {
+            calc_placidus_cusps(th, lat_rad, eps, mc * DEG_TO_RAD, asc * DEG_TO_RAD)
+        }
+        HouseSystem::Equal => equal_cusps(asc, 1),
+        HouseSystem::EqualMc => equal_cusps(mc, 10),
+        HouseSystem::WholeSign => equal_cusps((asc / 30.0).floor() * 30.0, 1),
+        HouseSystem::Porphyry => {
+            // Trisect the ecliptic arcs between the angles
+            let acmc = deg_norm(asc - mc);
+            let (upper, lower) = (acmc / 3.0, (180.0 - acmc) / 3.0);
+            quadrant_cusps(asc, mc, [mc + upper, mc + 2.0 * upper, asc + lower, asc + 2.0 * lower])
+        }
+        HouseSystem::Koch => {
+            // Trisect the ascensional difference of the MC at the birthplace
+            let sin_a = ((mc * DEG_TO_RAD).sin() * sin_eps / lat_rad.cos()).clamp(-1.0, 1.0);
+            let cos_a = (1.0 - sin_a * sin_a).sqrt();
+            let c = (tan_lat / cos_a).atan();
+            let ad3 = (c.sin() * sin_a).asin() * RAD_TO_DEG / 3.0;
+            quadrant_cusps(asc, mc, [
+                cusp_at(th + 30.0 - 2.0 * ad3, lat),
+                cusp_at(th + 60.0 - ad3, lat),
+                cusp_at(th + 120.0 + ad3, lat),
+                cusp_at(th + 150.0 + 2.0 * ad3, lat),
+            ])
+        }
+        HouseSystem::Regiomontanus => {
+            let fh1 = (tan_lat * 0.5).atan() * RAD_TO_DEG;
+            let fh2 = (tan_lat * (60.0 * DEG_TO_RAD).sin()).atan() * RAD_TO_DEG;
+            quadrant_cusps(asc, mc, [
+                cusp_at(th + 30.0, fh1),
+                cusp_at(th + 60.0, fh2),
+                cusp_at(th + 120.0, fh2),
+                cusp_at(th + 150.0, fh1),
+            ])
+        }
+        HouseSystem::Campanus => {
+            let sin_lat = lat_rad.sin();
+            let cos_lat = lat_rad.cos();
+            let fh1 = (sin_lat / 2.0).asin() * RAD_TO_DEG;
+            let fh2 = (3.0_f64.sqrt() / 2.0 * sin_lat).asin() * RAD_TO_DEG;
+            let (xh1, xh2) = if cos_lat.abs() < 1e-15 {
+                let pole = if lat > 0.0 { 90.0 } else { 270.0 };
+                (pole, pole)
+            } else {
+                (
+                    (3.0_f64.sqrt() / cos_lat).atan() * RAD_TO_DEG,
+                    (1.0 / 3.0_f64.sqrt() / cos_lat).atan() * RAD_TO_DEG,
+                )
+            };
+            quadrant_cusps(asc, mc, [
+                cusp_at(th + 90.0 - xh1, fh1),
+                cusp_at(th + 90.0 - xh2, fh2),
+                cusp_at(th + 90.0 + xh2, fh2),
+                cusp_at(th + 90.0 + xh1, fh1),
+            ])
+        }
+        HouseSystem::Alcabitius => {
+            // Trisect the diurnal and nocturnal semi-arcs of the Ascendant
+            let dec = ((asc * DEG_TO_RAD).sin() * sin_eps).asin();
+            let sda = (-tan_lat * dec.tan()).clamp(-1.0, 1.0).acos() * RAD_TO_DEG;
+            let (sd3, sn3) = (sda / 3.0, (180.0 - sda) / 3.0);
+            quadrant_cusps(asc, mc, [
+                cusp_at(th + sd3, 0.0),
+                cusp_at(th + 2.0 * sd3, 0.0),
+                cusp_at(th + 180.0 - 2.0 * sn3, 0.0),
+                cusp_at(th + 180.0 - sn3, 0.0),
+            ])
+        }
+        HouseSystem::Topocentric => {
+            let fh1 = (tan_lat / 3.0).atan() * RAD_TO_DEG;
+            let fh2 = (tan_lat * 2.0 / 3.0).atan() * RAD_TO_DEG;
+            quadrant_cusps(asc, mc, [
+                cusp_at(th + 30.0, fh1),
+                cusp_at(th + 60.0, fh2),
+                cusp_at(th + 120.0, fh2),
+                cusp_at(th + 150.0, fh1),
+            ])
+        }
+        HouseSystem::Morinus => equator_cusps(th, |ra| {
+            // Equator point rotated onto the ecliptic, along circles of longitude
+            let ecl = coord_trans(&pol_to_cart(&[ra * DEG_TO_RAD, 0.0, 1.0]), eps);
+            cart_to_pol(&ecl)[0] * RAD_TO_DEG
+        }),
+        HouseSystem::Meridian => equator_cusps(th, |ra| cusp_at(ra, 0.0)),
+    }
+}
+
+/// Twelve houses of 30° starting with cusp `first` at `start`
+fn equal_cusps(start: f64, first: usize) -> [f64; 13] {
+    let mut cusps = [0.0; 13];
+    for i in 0..12 {
+        cusps[(first - 1 + i) % 12 + 1] = deg_norm(start + 30.0 * i as f64);
+    }
+    cusps
+}
+
+/// Cusps of a quadrant system from the angles and cusps 11, 12, 2 and 3
+fn quadrant_cusps(asc: f64, mc: f64, [c11, c12, c2, c3]: [f64; 4]) -> [f64; 13] {
+    let mut cusps = [0.0; 13];
+    cusps[1] = deg_norm(asc);
+    cusps[10] = deg_norm(mc);
+    cusps[11] = deg_norm(c11);
+    cusps[12] = deg_norm(c12);
+    cusps[2] = deg_norm(c2);
+    cusps[3] = deg_norm(c3);
+    for i in [1, 2, 3, 10, 11, 12] {
+        cusps[(i + 5) % 12 + 1] = deg_norm(cusps[i] + 180.0);
+    }
+    cusps
+}
+
+/// Cusps at right ascensions ARMC + 30° (cusp 11), + 60° (cusp 12) and so
+/// on, each mapped onto the ecliptic by `to_ecliptic`
+fn equator_cusps(th: f64, to_ecliptic: impl Fn(f64) -> f64) -> [f64; 13] {
+    let mut cusps = [0.0; 13];
+    for i in 0..12 {
+        cusps[(10 + i) % 12 + 1] = deg_norm(to_ecliptic(deg_norm(th + 30.0 * (i + 1) as f64)));
+    }
+    cusps
+}
+
 /// Convert right ascension to ecliptic longitude for RA in [0°, 90°].
 ///
 /// Computes atan(sin(x) / (-tan(f)*sin(eps) + cos(eps)*cos(x)))
@@ -318,7 +676,7 @@ mod tests {
     fn test_houses_basic() {
         // Test at a known location and time
         let jd = julday_greg(2000, 1, 1, 12.0);
-        let houses = calc_houses_placidus(jd, 47.38, 8.54).unwrap();
+        let houses = calc_houses(jd, 47.38, 8.54, HouseSystem::Placidus).unwrap();
 
         // Basic sanity checks
         assert!(houses.ascendant >= 0.0 && houses.ascendant < 360.0);
@@ -338,7 +696,7 @@ mod tests {
         // London, 2000-01-01 12:00 UT
         // Swiss Ephemeris reference: ASC = 24.01°, MC = 280.47°
         let jd = julday_greg(2000, 1, 1, 12.0);
-        let houses = calc_houses_placidus(jd, 51.5074, -0.1278).unwrap();
+        let houses = calc_houses(jd, 51.5074, -0.1278, HouseSystem::Placidus).unwrap();
 
         // ASC should be ~24° (Aries), not ~204° (Libra)
         assert!((houses.ascendant - 24.01).abs() < 0.5,
@@ -351,6 +709,311 @@ mod tests {
             "MC was {}°, expected ~280.47°", houses.mc);
     }
 
+    /// Unit vector of an ecliptic longitude in the horizon frame (south, east, up)
+    fn horizon(lon: f64, th: f64, lat: f64, eps: f64) -> [f64; 3] {
+        let eq = cart_to_pol(&coord_trans(&pol_to_cart(&[lon * DEG_TO_RAD, 0.0, 1.0]), -eps));
+        let (ha, dec) = (th * DEG_TO_RAD - eq[0], eq[1]);
+        let (sin_lat, cos_lat) = (lat * DEG_TO_RAD).sin_cos();
+        let m = [dec.cos() * ha.cos(), dec.cos() * ha.sin(), dec.sin()];
+        [sin_lat * m[0] - cos_lat * m[2], -m[1], cos_lat * m[0] + sin_lat * m[2]]
+    }
+
+    #[test]
+    fn test_house_systems_at_equator() {
+        // On the equator every semi-arc is 90°: all quadrant systems based on
+        // time or on the equator collapse to the Meridian system
+        let jd = julday_greg(2024, 3, 1, 6.0);
+        let meridian = calc_houses(jd, 0.0, 30.0, HouseSystem::Meridian).unwrap();
+        for hsys in [
+            HouseSystem::Placidus,
+            HouseSystem::Koch,
+            HouseSystem::Regiomontanus,
+            HouseSystem::Campanus,
+            HouseSystem::Alcabitius,
+            HouseSystem::Topocentric,
+        ] {
+            let houses = calc_houses(jd, 0.0, 30.0, hsys).unwrap();
+            for i in [2, 3, 5, 6, 8, 9, 11, 12] {
+                assert!(angle_diff(houses.cusps[i], meridian.cusps[i]).abs() < 1e-6,
+                    "{:?} cusp {}: {} vs {}", hsys, i, houses.cusps[i], meridian.cusps[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_house_system_geometry() {
+        let jd = julday_greg(2000, 1, 1, 12.0);
+        let lat = 51.5074;
+        let eps = obliquity(jd + delta_t(jd));
+
+        // Campanus cusps divide the prime vertical: 60° and 30° above the eastern
+        // horizon for cusps 11 and 12, 30° and 60° below it for cusps 2 and 3
+        let houses = calc_houses(jd, lat, -0.1278, HouseSystem::Campanus).unwrap();
+        for (i, altitude) in [(11, 60.0), (12, 30.0), (2, -30.0), (3, -60.0)] {
+            let x = horizon(houses.cusps[i], houses.armc, lat, eps);
+            let angle = x[2].atan2(x[1]) * RAD_TO_DEG;
+            assert!((angle - altitude).abs() < 1e-6, "cusp {}: {}", i, angle);
+        }
+
+        // Regiomontanus cusps lie on great circles through the north and south
+        // points and the equator 30°, 60°, 120° and 150° east of the meridian
+        let houses = calc_houses(jd, lat, -0.1278, HouseSystem::Regiomontanus).unwrap();
+        for (i, hour_angle) in [(11, -30.0), (12, -60.0), (2, -120.0), (3, -150.0)] {
+            let x = horizon(houses.cusps[i], houses.armc, lat, eps);
+            let ha: f64 = hour_angle * DEG_TO_RAD;
+            let (sin_lat, cos_lat) = (lat * DEG_TO_RAD).sin_cos();
+            // Equator point in the horizon frame; the circle's normal is
+            // perpendicular to it and to the south point
+            let e = [sin_lat * ha.cos(), -ha.sin(), cos_lat * ha.cos()];
+            let normal = [0.0, e[2], -e[1]];
+            let dot = x[1] * normal[1] + x[2] * normal[2];
+            assert!(dot.abs() < 1e-9, "cusp {}: {}", i, dot);
+        }
+
+        // Alcabitius trisects the diurnal semi-arc of the Ascendant in right ascension
+        let houses = calc_houses(jd, lat, -0.1278, HouseSystem::Alcabitius).unwrap();
+        let ra = |lon: f64| {
+            let eq = coord_trans(&pol_to_cart(&[lon * DEG_TO_RAD, 0.0, 1.0]), -eps);
+            deg_norm(cart_to_pol(&eq)[0] * RAD_TO_DEG)
+        };
+        let sda = deg_norm(ra(houses.ascendant) - houses.armc);
+        assert!(angle_diff(ra(houses.cusps[11]), houses.armc + sda / 3.0).abs() < 1e-6);
+        assert!(angle_diff(ra(houses.cusps[12]), houses.armc + 2.0 * sda / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ecliptic_house_systems() {
+        let jd = julday_greg(2000, 1, 1, 12.0);
+        let placidus = calc_houses(jd, 51.5074, -0.1278, HouseSystem::Placidus).unwrap();
+
+        let equal = calc_houses(jd, 51.5074, -0.1278, HouseSystem::Equal).unwrap();
+        let equal_mc = calc_houses(jd, 51.5074, -0.1278, HouseSystem::EqualMc).unwrap();
+        let whole = calc_houses(jd, 51.5074, -0.1278, HouseSystem::WholeSign).unwrap();
+        let porphyry = calc_houses(jd, 51.5074, -0.1278, HouseSystem::Porphyry).unwrap();
+        for i in 1..=12 {
+            let n = (i as f64 - 1.0) * 30.0;
+            assert!(angle_diff(equal.cusps[i], placidus.ascendant + n).abs() < 1e-9);
+            assert!(angle_diff(equal_mc.cusps[i], placidus.mc + n + 90.0).abs() < 1e-9);
+            assert!(angle_diff(whole.cusps[i], n).abs() < 1e-9); // Ascendant in Aries
+        }
+        // Porphyry keeps the angles and divides each quadrant in three
+        assert!(angle_diff(porphyry.cusps[1], placidus.ascendant).abs() < 1e-9);
+        assert!(angle_diff(porphyry.cusps[10], placidus.mc).abs() < 1e-9);
+        let quadrant = deg_norm(porphyry.cusps[1] - porphyry.cusps[10]);
+        assert!(angle_diff(porphyry.cusps[11], porphyry.cusps[10] + quadrant / 3.0).abs() < 1e-9);
+        assert!(angle_diff(porphyry.cusps[3], porphyry.cusps[4] - (180.0 - quadrant) / 3.0).abs() < 1e-9);
+
+        // Topocentric stays within a fraction of a degree of Placidus at mid latitudes
+        let topo = calc_houses(jd, 51.5074, -0.1278, HouseSystem::Topocentric).unwrap();
+        for i in 1..=12 {
+            assert!(angle_diff(topo.cusps[i], placidus.cusps[i]).abs() < 0.6);
+        }
+
+        for &hsys in HouseSystem::all() {
+            assert_eq!(HouseSystem::from_code(hsys.code()), Some(hsys));
+        }
+        assert_eq!(HouseSystem::from_code('a'), Some(HouseSystem::Equal));
+        assert_eq!(HouseSystem::from_code('Z'), None);
+    }
+
+    #[test]
+    fn test_extra_angles() {
+        let jd = julday_greg(2000, 1, 1, 12.0);
+        let london = calc_houses(jd, 51.5074, -0.1278, HouseSystem::Placidus).unwrap();
+        let equator = calc_houses(jd, 0.0, -0.1278, HouseSystem::Placidus).unwrap();
+
+        // The Equatorial Ascendant is the Ascendant at latitude 0, which is
+        // 90° from the MC measured in right ascension
+        assert!(angle_diff(london.equatorial_ascendant, equator.ascendant).abs() < 1e-9);
+        let eps = obliquity(jd + delta_t(jd));
+        let ra = |lon: f64| {
+            let eq = coord_trans(&pol_to_cart(&[lon * DEG_TO_RAD, 0.0, 1.0]), -eps);
+            cart_to_pol(&eq)[0] * RAD_TO_DEG
+        };
+        assert!(angle_diff(ra(london.equatorial_ascendant), london.armc + 90.0).abs() < 1e-6);
+
+        // The Koch co-ascendant is opposite the polar ascendant
+        assert!(angle_diff(london.co_ascendant_koch, london.polar_ascendant + 180.0).abs() < 1e-9);
+        // The polar ascendant is the Ascendant twelve sidereal hours earlier;
+        // Munkasey's co-ascendant uses the co-latitude
+        let earlier = calc_houses(jd - 0.5 / 1.0027379, 51.5074, -0.1278, HouseSystem::Placidus).unwrap();
+        assert!(angle_diff(london.polar_ascendant, earlier.ascendant).abs() < 0.01);
+        let (sin_eps, cos_eps) = eps.sin_cos();
+        let munkasey = asc1_deg(london.armc + 90.0, 90.0 - 51.5074, sin_eps, cos_eps);
+        assert!(angle_diff(london.co_ascendant_munkasey, munkasey).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_houses_armc() {
+        let jd = julday_greg(2000, 1, 1, 12.0);
+        let eps = obliquity(jd + delta_t(jd)) * RAD_TO_DEG;
+        for &hsys in HouseSystem::all() {
+            let houses = calc_houses(jd, 51.5074, -0.1278, hsys).unwrap();
+            let from_armc = houses_armc(houses.armc, 51.5074, eps, hsys).unwrap();
+            for i in 1..=12 {
+                assert!(angle_diff(houses.cusps[i], from_armc.cusps[i]).abs() < 1e-9);
+            }
+            assert!(angle_diff(houses.ascendant, from_armc.ascendant).abs() < 1e-9);
+        }
+
+        // ARMC 0 puts the MC at 0° Aries whatever the latitude and obliquity
+        let houses = houses_armc(360.0, 40.0, 23.0, HouseSystem::Regiomontanus).unwrap();
+        assert!(angle_diff(houses.mc, 0.0).abs() < 1e-9);
+        assert_eq!(houses.armc, 0.0);
+        assert!(houses_armc(0.0, 91.0, 23.44, HouseSystem::Placidus).is_err());
+    }
+
+    #[test]
+    fn test_house_pos() {
+        let (th, lat, eps) = (123.4, 47.38, 23.44);
+        for &hsys in HouseSystem::all().iter().filter(|&&h| h != HouseSystem::Gauquelin) {
+            let houses = houses_armc(th, lat, eps, hsys).unwrap();
+            for i in 1..=12 {
+                // A point on the ecliptic at cusp i, and a third of the way into house i
+                let pos = house_pos(th, lat, eps, hsys, houses.cusps[i], 0.0).unwrap();
+                assert!(angle_diff(pos * 30.0, i as f64 * 30.0).abs() < 1e-6, "{:?} {}: {}", hsys, i, pos);
+                let third = houses.cusps[i] + angle_diff(houses.cusps[i % 12 + 1], houses.cusps[i]) / 3.0;
+                let pos = house_pos(th, lat, eps, hsys, third, 0.0).unwrap();
+                assert!(pos > i as f64 && pos < i as f64 + 1.0, "{:?} {}: {}", hsys, i, pos);
+            }
+        }
+
+        // The house circles meet on the meridian: a body with latitude at its
+        // lower culmination is on the fourth cusp, while its longitude is not the IC
+        let eq = pol_to_cart(&[(th + 180.0) * DEG_TO_RAD, 20.0 * DEG_TO_RAD, 1.0]);
+        let ecl = cart_to_pol(&coord_trans(&eq, eps * DEG_TO_RAD));
+        let (lon, lat_body) = (ecl[0] * RAD_TO_DEG, ecl[1] * RAD_TO_DEG);
+        for hsys in [HouseSystem::Placidus, HouseSystem::Regiomontanus, HouseSystem::Campanus, HouseSystem::Alcabitius] {
+            let pos = house_pos(th, lat, eps, hsys, lon, lat_body).unwrap();
+            assert!((pos - 4.0).abs() < 1e-9, "{:?}: {}", hsys, pos);
+            let by_lon = house_pos(th, lat, eps, hsys, lon, 0.0).unwrap();
+            assert!((by_lon - 4.0).abs() > 0.01, "{:?}: {}", hsys, by_lon);
+        }
+
+        // A circumpolar body stays in the houses above the horizon, one that
+        // never rises in those below it
+        for th in (0..360).step_by(15).map(|th| th as f64 + 0.5) {
+            let pos = house_pos(th, 60.0, eps, HouseSystem::Placidus, 90.0, 10.0).unwrap();
+            assert!((7.0..13.0).contains(&pos), "ARMC {}: {}", th, pos);
+            let pos = house_pos(th, 60.0, eps, HouseSystem::Placidus, 270.0, -10.0).unwrap();
+            assert!((1.0..7.0).contains(&pos), "ARMC {}: {}", th, pos);
+        }
+        assert!(house_pos(th, 60.0, eps, HouseSystem::Regiomontanus, 90.0, 10.0).is_ok());
+    }
+
+    #[test]
+    fn test_gauquelin_sectors() {
+        let (th, lat, eps) = (123.4, 47.38, 23.44);
+        let houses = houses_armc(th, lat, eps, HouseSystem::Gauquelin).unwrap();
+        let placidus = houses_armc(th, lat, eps, HouseSystem::Placidus).unwrap();
+        assert_eq!(houses.sectors.len(), 37);
+        assert!(placidus.sectors.is_empty());
+        for i in 1..=12 {
+            assert!(angle_diff(houses.cusps[i], placidus.cusps[i]).abs() < 1e-9);
+            // Sectors run clockwise, three to a house
+            let sector = (13 - i) % 12 * 3 + 1;
+            assert!(angle_diff(houses.sectors[sector], placidus.cusps[i]).abs() < 1e-5, "{}", i);
+        }
+        for g in 1..=36 {
+            assert!(angle_diff(houses.sectors[g], houses.sectors[g % 36 + 1]) > 0.0, "{}", g);
+            let pos = house_pos(th, lat, eps, HouseSystem::Gauquelin, houses.sectors[g], 0.0).unwrap();
+            assert!(angle_diff(pos * 10.0, g as f64 * 10.0).abs() < 1e-4, "{}: {}", g, pos);
+        }
+
+        // The Sun culminating on 2024 June 21 begins sector 10
+        let jd = julday_greg(2024, 6, 21, 12.0);
+        let sun = crate::calc_ut_flags(jd, Planet::Sun, SEFLG_EQUATORIAL).unwrap();
+        let lon = deg_norm_180(sun.longitude - armc(jd, 0.0));
+        let sector = gauquelin_sector(jd, Planet::Sun, lat, lon, true).unwrap();
+        assert!((sector - 10.0).abs() < 0.01, "{}", sector);
+        // and moves on clockwise, a ninth of its 118° semi-arc in 52 minutes
+        let later = gauquelin_sector(jd + 52.5 / 1440.0, Planet::Sun, lat, lon, false).unwrap();
+        assert!((later - 11.0).abs() < 0.01, "{}", later);
+    }
+
+    #[test]
+    fn test_gauquelin_high_latitude() {
+        // At Trondheim the Moon does not set on some days of 2025, and does not
+        // rise on others; it still has a sector every day
+        let (lat, lon) = (63.43, 10.40);
+        let mut circumpolar = 0;
+        for day in 0..365 {
+            let jd = julday_greg(2025, 1, 1, 0.0) + day as f64;
+            let sector = gauquelin_sector(jd, Planet::Moon, lat, lon, true).unwrap();
+            assert!((1.0..37.0).contains(&sector), "day {}: {}", day, sector);
+            let dec = crate::calc_ut_flags(jd, Planet::Moon, SEFLG_EQUATORIAL).unwrap().latitude;
+            if dec > 90.0 - lat {
+                // Above the horizon all day: sectors 1-18
+                assert!(sector < 19.0, "day {}: {}", day, sector);
+                circumpolar += 1;
+            } else if dec < lat - 90.0 {
+                assert!(sector >= 19.0, "day {}: {}", day, sector);
+                circumpolar += 1;
+            }
+        }
+        assert!(circumpolar > 50, "{}", circumpolar);
+    }
+
+    #[test]
+    fn test_polar_fallback() {
+        let jd = julday_greg(2024, 3, 1, 6.0);
+        // Tromsø lies inside the Arctic circle
+        let porphyry = calc_houses(jd, 69.65, 18.96, HouseSystem::Porphyry).unwrap();
+        for hsys in [HouseSystem::Placidus, HouseSystem::Koch] {
+            let houses = calc_houses(jd, 69.65, 18.96, hsys).unwrap();
+            assert_eq!(houses.system, HouseSystem::Porphyry);
+            assert_eq!(houses.cusps, porphyry.cusps);
+        }
+        // Systems defined everywhere are kept, and so is Placidus further south.
+        // At this hour the Regiomontanus circles meet the ecliptic out of
+        // order; three hours later they do not
+        let morinus = calc_houses(jd, 69.65, 18.96, HouseSystem::Morinus).unwrap();
+        assert_eq!(morinus.system, HouseSystem::Morinus);
+        let regio = calc_houses(jd, 69.65, 18.96, HouseSystem::Regiomontanus).unwrap();
+        assert_eq!(regio.system, HouseSystem::Porphyry);
+        let regio = calc_houses(jd + 0.125, 69.65, 18.96, HouseSystem::Regiomontanus).unwrap();
+        assert_eq!(regio.system, HouseSystem::Regiomontanus);
+        let oslo = calc_houses(jd, 59.91, 10.75, HouseSystem::Placidus).unwrap();
+        assert_eq!(oslo.system, HouseSystem::Placidus);
+        let south = calc_houses(jd, -70.0, 0.0, HouseSystem::Koch).unwrap();
+        assert_eq!(south.system, HouseSystem::Porphyry);
+    }
+
+    #[test]
+    fn test_polar_quadrants() {
+        // At Tromsø the Ascendant lies west of the MC for part of the day
+        for th in 0..360 {
+            let houses = houses_armc(th as f64, 69.65, 23.44, HouseSystem::Placidus).unwrap();
+            assert_eq!(houses.system, HouseSystem::Porphyry);
+            assert!(cusps_in_order(&houses.cusps), "ARMC {}: {:?}", th, houses.cusps);
+            assert!(deg_norm(houses.ascendant - houses.mc) < 180.0, "ARMC {}", th);
+        }
+    }
+
+    #[test]
+    fn test_polar_house_circles() {
+        // Where the house circles meet the ecliptic out of order the cusps fall
+        // back to Porphyry; either way, house_pos agrees with the cusps
+        for hsys in [HouseSystem::Regiomontanus, HouseSystem::Campanus, HouseSystem::Topocentric, HouseSystem::Alcabitius] {
+            for th in (0..360).map(|th| th as f64) {
+                let houses = houses_armc(th, 69.65, 23.44, hsys).unwrap();
+                assert!(cusps_in_order(&houses.cusps), "{:?} ARMC {}: {:?}", hsys, th, houses.cusps);
+                for i in 1..=12 {
+                    let pos = house_pos(th, 69.65, 23.44, hsys, houses.cusps[i], 0.0).unwrap();
+                    assert!(angle_diff(pos * 30.0, i as f64 * 30.0).abs() < 1e-4, "{:?} ARMC {} cusp {}", hsys, th, i);
+                    let third = houses.cusps[i] + deg_norm(houses.cusps[i % 12 + 1] - houses.cusps[i]) / 3.0;
+                    let pos = house_pos(th, 69.65, 23.44, hsys, third, 0.0).unwrap();
+                    assert!(pos > i as f64 && pos < i as f64 + 1.0, "{:?} ARMC {} house {}: {}", hsys, th, i, pos);
+                }
+            }
+        }
+        // Regiomontanus is kept for most of the day
+        let kept = (0..360).filter(|&th| {
+            houses_armc(th as f64, 69.65, 23.44, HouseSystem::Regiomontanus).unwrap().system == HouseSystem::Regiomontanus
+        });
+        assert!(kept.count() > 270);
+    }
+
     #[test]
     fn test_mc_calculation() {
         // At ARMC = 0, MC should be 0° (Aries point)
@@ -367,6 +1030,6 @@ mod tests {
         let asc = calc_ascendant(0.0, lat, eps);
 
         // ASC should be in a reasonable range
-        assert!(asc >= 0.0 && asc < TWOPI);
+        assert!((0.0..TWOPI).contains(&asc));
     }
 }
@@ -3,6 +3,26 @@
 //! Standard astronomical algorithms for Julian Day conversion.
 
 use crate::constants::SE_GREG_CAL;
+use crate::{delta_t, Error, Result};
+
+/// Days ending in a leap second (23:59:60 UTC), as YYYYMMDD
+///
+/// TAI - UTC was 10 s when leap seconds started in 1972 and grows by one
+/// second after each of these days.
+const LEAP_SECONDS: &[i32] = &[
+    19720630, 19721231, 19731231, 19741231, 19751231, 19761231, 19771231, 19781231, 19791231,
+    19810630, 19820630, 19830630, 19850630, 19871231, 19891231, 19901231, 19920630, 19930630,
+    19940630, 19951231, 19970630, 19981231, 20051231, 20081231, 20120630, 20150630, 20161231,
+];
+
+/// 1972-01-01 00:00 UTC, start of UTC with leap seconds
+const JD_UTC_LEAP_START: f64 = 2441317.5;
+
+/// TAI - UTC in seconds at 1972-01-01
+const TAI_UTC_1972: f64 = 10.0;
+
+/// TT - TAI in seconds
+const TT_TAI: f64 = 32.184;
 
 /// Convert calendar date to Julian Day number
 ///
@@ -107,6 +127,94 @@ pub fn year_to_jd(year: f64) -> f64 {
     crate::constants::J2000 + (year - 2000.0) * 365.25
 }
 
+/// Convert a UTC date to Julian days in TT (ET) and UT1
+///
+/// Mirrors `swe_utc_to_jd`: returns `(jd_et, jd_ut)`. From 1972 on, TT
+/// follows from the leap-second table; `second` may be 60 at the end of a
+/// day with a leap second. Before 1972, the time is taken as UT1.
+///
+/// Returns `Error::InvalidDate` for dates or times that do not exist.
+pub fn utc_to_jd(
+    year: i32,
+    month: i32,
+    day: i32,
+    hour: i32,
+    minute: i32,
+    second: f64,
+    gregflag: i32,
+) -> Result<(f64, f64)> {
+    let jd_day = julday(year, month, day, 0.0, gregflag);
+    let (y, m, d, _) = revjul(jd_day, gregflag);
+    if (y, m, d) != (year, month, day)
+        || !(0..24).contains(&hour)
+        || !(0..60).contains(&minute)
+        || !(0.0..61.0).contains(&second)
+    {
+        return Err(Error::InvalidDate);
+    }
+    let day_seconds = hour as f64 * 3600.0 + minute as f64 * 60.0 + second;
+
+    if jd_day < JD_UTC_LEAP_START {
+        let jd_ut = jd_day + day_seconds / 86400.0;
+        return Ok((jd_ut + delta_t(jd_ut), jd_ut));
+    }
+
+    let leaps_before = leap_second_days().take_while(|&leap_day| leap_day < jd_day).count();
+    let ends_in_leap_second = leap_second_days().any(|leap_day| leap_day == jd_day);
+    if second >= 60.0 && !(ends_in_leap_second && hour == 23 && minute == 59) {
+        return Err(Error::InvalidDate);
+    }
+
+    let tai_utc = TAI_UTC_1972 + leaps_before as f64;
+    let jd_et = jd_day + (day_seconds + tai_utc + TT_TAI) / 86400.0;
+    // UT1 = TT - delta-T, with delta-T evaluated at UT1
+    let jd_ut = jd_et - delta_t(jd_et - delta_t(jd_et));
+    Ok((jd_et, jd_ut))
+}
+
+/// Convert a Julian day in TT (ET) to a UTC date
+///
+/// Mirrors `swe_jdet_to_utc`: returns `(year, month, day, hour, minute,
+/// second)`, with `second` 60 or more inside a leap second. Before 1972, the
+/// result is UT1. For a Julian day in UT1, pass `jd_ut + delta_t(jd_ut)`.
+pub fn jd_to_utc(jd_et: f64, gregflag: i32) -> (i32, i32, i32, i32, i32, f64) {
+    let mut jd_utc = jd_et - delta_t(jd_et - delta_t(jd_et));
+    let mut extra_second = 0.0;
+
+    if jd_utc >= JD_UTC_LEAP_START {
+        let mut tai_utc = TAI_UTC_1972;
+        for leap_day in leap_second_days() {
+            // Start of the leap second 23:59:60 on `leap_day`, in TT
+            let leap_start = leap_day + 1.0 + (tai_utc + TT_TAI) / 86400.0;
+            if jd_et < leap_start {
+                break;
+            }
+            if jd_et < leap_start + 1.0 / 86400.0 {
+                // Report the leap second as 23:59:59 plus one second
+                tai_utc += 1.0;
+                extra_second = 1.0;
+                break;
+            }
+            tai_utc += 1.0;
+        }
+        jd_utc = jd_et - (tai_utc + TT_TAI) / 86400.0;
+    }
+
+    let (year, month, day, hours) = revjul(jd_utc, gregflag);
+    let hour = hours.floor();
+    let minutes = (hours - hour) * 60.0;
+    let minute = minutes.floor();
+    let second = (minutes - minute) * 60.0 + extra_second;
+    (year, month, day, hour as i32, minute as i32, second)
+}
+
+/// Julian days (0h UTC) of the days in [`LEAP_SECONDS`]
+fn leap_second_days() -> impl Iterator<Item = f64> {
+    LEAP_SECONDS
+        .iter()
+        .map(|&ymd| julday(ymd / 10000, ymd / 100 % 100, ymd % 100, 0.0, SE_GREG_CAL))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,4 +254,34 @@ mod tests {
         let jd = julday(1999, 12, 31, 0.0, SE_GREG_CAL);
         assert!((jd - 2451543.5).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_utc_to_jd() {
+        // 2000-01-01 12:00 UTC: TAI - UTC = 32 s, so TT = UTC + 64.184 s
+        let (jd_et, jd_ut) = utc_to_jd(2000, 1, 1, 12, 0, 0.0, SE_GREG_CAL).unwrap();
+        assert!(((jd_et - 2451545.0) * 86400.0 - 64.184).abs() < 1e-4);
+        // UT1 is within a second of UTC
+        assert!(((jd_ut - 2451545.0) * 86400.0).abs() < 1.0);
+
+        // The leap second at the end of 2016
+        let (leap, _) = utc_to_jd(2016, 12, 31, 23, 59, 60.5, SE_GREG_CAL).unwrap();
+        let (after, _) = utc_to_jd(2017, 1, 1, 0, 0, 0.0, SE_GREG_CAL).unwrap();
+        assert!(((after - leap) * 86400.0 - 0.5).abs() < 1e-4);
+
+        assert!(utc_to_jd(2015, 12, 31, 23, 59, 60.0, SE_GREG_CAL).is_err());
+        assert!(utc_to_jd(2023, 2, 29, 12, 0, 0.0, SE_GREG_CAL).is_err());
+        assert!(utc_to_jd(2023, 2, 28, 24, 0, 0.0, SE_GREG_CAL).is_err());
+    }
+
+    #[test]
+    fn test_jd_to_utc_roundtrip() {
+        let times = [(1950, 6, 1, 3, 4, 5.5), (1999, 12, 31, 23, 59, 59.25),
+                     (2016, 12, 31, 23, 59, 60.5), (2024, 3, 10, 18, 30, 0.75)];
+        for &(y, m, d, h, mi, s) in &times {
+            let (jd_et, _) = utc_to_jd(y, m, d, h, mi, s, SE_GREG_CAL).unwrap();
+            let (y2, m2, d2, h2, mi2, s2) = jd_to_utc(jd_et, SE_GREG_CAL);
+            assert_eq!((y2, m2, d2, h2, mi2), (y, m, d, h, mi));
+            assert!((s2 - s).abs() < 1e-3, "{} vs {}", s2, s);
+        }
+    }
 }
@@ -2,21 +2,34 @@
 //!
 //! This library provides calculations for:
 //! - Planet positions (Sun, Moon, Mercury through Pluto)
-//! - True lunar node
-//! - House cusps (Placidus system)
+//! - Chiron, Pholus, Ceres, Pallas, Juno and Vesta
+//! - Mean and true lunar node, mean and osculating lunar apogee (Lilith)
+//! - Fixed stars
+//! - House cusps (Placidus, Koch, Regiomontanus and nine other systems) and Gauquelin sectors
+//! - Sidereal zodiac with the common ayanamsas
 //!
-//! Uses VSOP87 theory for planets, ELP2000 for Moon.
+//! Uses truncated VSOP87D series for the planets, Meeus chapter 37 for Pluto
+//! and ELP2000 (Meeus chapter 47) for the Moon.
 //! Licensed under GPL-3.0.
 
 #![allow(clippy::excessive_precision)]
 
 pub mod constants;
+pub mod context;
+pub mod deltat;
 pub mod julian;
 pub mod math;
 pub mod planets;
+pub mod asteroids;
+pub mod vsop87;
 pub mod moon;
+pub mod nutation;
+pub mod precession;
+pub mod topo;
 pub mod houses;
 pub mod nodes;
+pub mod fixstars;
+pub mod sidereal;
 pub mod astrology;
 
 #[cfg(feature = "wasm")]
@@ -25,8 +38,17 @@ pub mod wasm;
 pub use constants::*;
 pub use julian::*;
 pub use math::deg_norm;
+pub use context::{
+    clear_orbits, get_delta_t_userdef, get_precision, get_sid_mode, get_topo, register_orbit,
+    set_delta_t_userdef, set_precision, set_sid_mode, set_topo,
+};
+pub use fixstars::{fixstar, fixstar_ut, FixedStar};
 
 /// Planet identifiers (matching Swiss Ephemeris)
+///
+/// Use [`Planet::id`] for the Swiss number. Since [`Planet::Custom`] carries
+/// the index of a registered body, the enum is no longer field-less and
+/// `planet as i32` does not compile.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(i32)]
 pub enum Planet {
@@ -40,11 +62,50 @@ pub enum Planet {
     Uranus = 7,
     Neptune = 8,
     Pluto = 9,
+    MeanNode = 10,
     TrueNode = 11,
+    MeanApogee = 12,
+    OscuApogee = 13,
     Earth = 14,
+    Chiron = 15,
+    Pholus = 16,
+    Ceres = 17,
+    Pallas = 18,
+    Juno = 19,
+    Vesta = 20,
+    /// Body registered with [`register_orbit`], by registration order
+    Custom(u16) = 40,
 }
 
 impl Planet {
+    /// Swiss Ephemeris body number
+    pub fn id(self) -> i32 {
+        match self {
+            Planet::Sun => SE_SUN,
+            Planet::Moon => SE_MOON,
+            Planet::Mercury => SE_MERCURY,
+            Planet::Venus => SE_VENUS,
+            Planet::Mars => SE_MARS,
+            Planet::Jupiter => SE_JUPITER,
+            Planet::Saturn => SE_SATURN,
+            Planet::Uranus => SE_URANUS,
+            Planet::Neptune => SE_NEPTUNE,
+            Planet::Pluto => SE_PLUTO,
+            Planet::MeanNode => SE_MEAN_NODE,
+            Planet::TrueNode => SE_TRUE_NODE,
+            Planet::MeanApogee => SE_MEAN_APOG,
+            Planet::OscuApogee => SE_OSCU_APOG,
+            Planet::Earth => SE_EARTH,
+            Planet::Chiron => SE_CHIRON,
+            Planet::Pholus => SE_PHOLUS,
+            Planet::Ceres => SE_CERES,
+            Planet::Pallas => SE_PALLAS,
+            Planet::Juno => SE_JUNO,
+            Planet::Vesta => SE_VESTA,
+            Planet::Custom(n) => SE_FICT_OFFSET + n as i32,
+        }
+    }
+
     pub fn from_i32(id: i32) -> Option<Self> {
         match id {
             0 => Some(Planet::Sun),
@@ -57,8 +118,22 @@ impl Planet {
             7 => Some(Planet::Uranus),
             8 => Some(Planet::Neptune),
             9 => Some(Planet::Pluto),
+            10 => Some(Planet::MeanNode),
             11 => Some(Planet::TrueNode),
+            12 => Some(Planet::MeanApogee),
+            13 => Some(Planet::OscuApogee),
             14 => Some(Planet::Earth),
+            15 => Some(Planet::Chiron),
+            16 => Some(Planet::Pholus),
+            17 => Some(Planet::Ceres),
+            18 => Some(Planet::Pallas),
+            19 => Some(Planet::Juno),
+            20 => Some(Planet::Vesta),
+            // Registered bodies only
+            SE_FICT_OFFSET..=SE_FICT_MAX => {
+                let index = (id - SE_FICT_OFFSET) as u16;
+                context::get_orbit(index).map(|_| Planet::Custom(index))
+            }
             _ => None,
         }
     }
@@ -75,10 +150,43 @@ impl Planet {
             Planet::Uranus,
             Planet::Neptune,
             Planet::Pluto,
+            Planet::MeanNode,
             Planet::TrueNode,
+            Planet::MeanApogee,
+            Planet::OscuApogee,
+            Planet::Chiron,
+            Planet::Pholus,
+            Planet::Ceres,
+            Planet::Pallas,
+            Planet::Juno,
+            Planet::Vesta,
         ]
     }
 
+    /// Is this one of the lunar nodes?
+    pub fn is_node(self) -> bool {
+        matches!(self, Planet::MeanNode | Planet::TrueNode)
+    }
+
+    /// Is this a point of the lunar orbit (a node or the apogee)?
+    pub fn is_lunar_point(self) -> bool {
+        self.is_node() || matches!(self, Planet::MeanApogee | Planet::OscuApogee)
+    }
+
+    /// Is this one of the bodies calculated from orbital elements (see [`asteroids`])?
+    pub fn is_minor_body(self) -> bool {
+        matches!(
+            self,
+            Planet::Chiron
+                | Planet::Pholus
+                | Planet::Ceres
+                | Planet::Pallas
+                | Planet::Juno
+                | Planet::Vesta
+                | Planet::Custom(_)
+        )
+    }
+
     /// Planets valid for heliocentric calculations (Earth + Mercury through Pluto)
     pub fn heliocentric_planets() -> &'static [Planet] {
         &[
@@ -95,6 +203,41 @@ impl Planet {
     }
 }
 
+/// Series truncation level of the planet and Moon theories
+///
+/// Selected with [`set_precision`]. Lower tiers evaluate fewer periodic terms,
+/// trading accuracy for speed. The bounds below are the largest error in
+/// geocentric longitude and latitude caused by the dropped terms of both the
+/// planet's and the Earth's series, anywhere in the supported -3000..+3000
+/// range for the planets (at most 9.5" for `Standard`, Venus around -2900,
+/// and 4.2' for `Fast`, Mars around 2600). For the Moon `High` also adds
+/// quartic secular terms, so the bounds only hold over
+/// [`moon::STANDARD_YEARS`] (500-3000) and [`moon::FAST_YEARS`] (-1500 to 3000).
+///
+/// Without the `full-series` Cargo feature only the planetary terms of `Fast`
+/// are compiled in, and `Standard` and `High` give the same planets as `Fast`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Precision {
+    /// Largest terms only (about 1/4 of the planetary series), within 5 arcminutes
+    Fast,
+    /// Drops terms below 1e-6 radian, within 10 arcseconds
+    #[default]
+    Standard,
+    /// Every tabulated term, plus the quartic lunar terms (see [`moon`])
+    High,
+}
+
+impl Precision {
+    /// Documented truncation error bound relative to `High` (arcseconds)
+    pub fn max_error_arcsec(self) -> f64 {
+        match self {
+            Precision::Fast => 300.0,
+            Precision::Standard => 10.0,
+            Precision::High => 0.0,
+        }
+    }
+}
+
 /// Position result with longitude, latitude, distance, and speeds
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Position {
@@ -129,11 +272,255 @@ impl Position {
     }
 }
 
+/// Which lunar orbit [`nod_aps_ut`] takes the nodes and apsides from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NodApsMethod {
+    /// Mean orbit: mean node and mean apogee (Swiss `SE_NODBIT_MEAN`)
+    #[default]
+    Mean,
+    /// Osculating orbit of the Moon at that instant (Swiss `SE_NODBIT_OSCU`)
+    Osculating,
+}
+
+/// Nodes and apsides of the lunar orbit
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NodAps {
+    /// Ascending (north) node
+    pub ascending: Position,
+    /// Descending (south) node
+    pub descending: Position,
+    /// Perigee
+    pub perigee: Position,
+    /// Apogee (Black Moon Lilith)
+    pub apogee: Position,
+}
+
+/// Rectangular position and velocity
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StateVector {
+    /// Position in AU
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    /// Velocity in AU/day
+    pub speed_x: f64,
+    pub speed_y: f64,
+    pub speed_z: f64,
+}
+
+impl From<Position> for StateVector {
+    fn from(pos: Position) -> Self {
+        let cart = math::pol_to_cart_speed(&[
+            pos.longitude * DEG_TO_RAD,
+            pos.latitude * DEG_TO_RAD,
+            pos.distance,
+            pos.speed_longitude * DEG_TO_RAD,
+            pos.speed_latitude * DEG_TO_RAD,
+            pos.speed_distance,
+        ]);
+        StateVector {
+            x: cart[0],
+            y: cart[1],
+            z: cart[2],
+            speed_x: cart[3],
+            speed_y: cart[4],
+            speed_z: cart[5],
+        }
+    }
+}
+
+/// Keplerian elements of a body for [`register_orbit`]
+///
+/// Heliocentric osculating elements referred to the ecliptic and equinox of
+/// J2000, as given by the MPC and JPL. Angles in degrees.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct KeplerElements {
+    /// Epoch of the elements, Julian day (TT)
+    pub epoch: f64,
+    /// Semi-major axis in AU
+    pub semi_major: f64,
+    /// Eccentricity, below 1
+    pub eccentricity: f64,
+    /// Inclination
+    pub inclination: f64,
+    /// Longitude of the ascending node
+    pub asc_node: f64,
+    /// Longitude of perihelion (node plus argument of perihelion)
+    pub lon_perihelion: f64,
+    /// Mean anomaly at the epoch
+    pub mean_anomaly: f64,
+}
+
+/// Geographic position of an observer for topocentric calculations
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Observer {
+    /// Geodetic latitude in degrees (north positive)
+    pub latitude: f64,
+    /// Geographic longitude in degrees (east positive)
+    pub longitude: f64,
+    /// Altitude above the WGS84 ellipsoid in meters
+    pub altitude: f64,
+}
+
+/// House systems, identified by their Swiss Ephemeris letter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HouseSystem {
+    /// Placidus ('P'): trisected semi-arcs
+    #[default]
+    Placidus,
+    /// Koch ('K'): birthplace houses, trisected ascensional difference of the MC
+    Koch,
+    /// Equal houses from the Ascendant ('E', also 'A')
+    Equal,
+    /// Equal houses from the MC ('D')
+    EqualMc,
+    /// Whole sign ('W'): the sign of the Ascendant is the first house
+    WholeSign,
+    /// Porphyry ('O'): trisected ecliptic quadrants
+    Porphyry,
+    /// Regiomontanus ('R'): equal divisions of the equator
+    Regiomontanus,
+    /// Campanus ('C'): equal divisions of the prime vertical
+    Campanus,
+    /// Alcabitius ('B'): trisected semi-arcs of the Ascendant
+    Alcabitius,
+    /// Morinus ('M'): equal divisions of the equator, projected from the ecliptic poles
+    Morinus,
+    /// Topocentric ('T', Polich-Page)
+    Topocentric,
+    /// Meridian ('X', axial rotation): equal divisions of the equator along hour circles
+    Meridian,
+    /// Gauquelin sectors ('G'): semi-arcs divided in nine, 36 sectors numbered
+    /// clockwise from the Ascendant
+    Gauquelin,
+}
+
+impl HouseSystem {
+    /// House system for a Swiss Ephemeris letter, case-insensitive
+    pub fn from_code(code: char) -> Option<Self> {
+        Some(match code.to_ascii_uppercase() {
+            'P' => HouseSystem::Placidus,
+            'K' => HouseSystem::Koch,
+            'E' | 'A' => HouseSystem::Equal,
+            'D' => HouseSystem::EqualMc,
+            'W' => HouseSystem::WholeSign,
+            'O' => HouseSystem::Porphyry,
+            'R' => HouseSystem::Regiomontanus,
+            'C' => HouseSystem::Campanus,
+            'B' => HouseSystem::Alcabitius,
+            'M' => HouseSystem::Morinus,
+            'T' => HouseSystem::Topocentric,
+            'X' => HouseSystem::Meridian,
+            'G' => HouseSystem::Gauquelin,
+            _ => return None,
+        })
+    }
+
+    /// Swiss Ephemeris letter of the house system
+    pub fn code(self) -> char {
+        match self {
+            HouseSystem::Placidus => 'P',
+            HouseSystem::Koch => 'K',
+            HouseSystem::Equal => 'E',
+            HouseSystem::EqualMc => 'D',
+            HouseSystem::WholeSign => 'W',
+            HouseSystem::Porphyry => 'O',
+            HouseSystem::Regiomontanus => 'R',
+            HouseSystem::Campanus => 'C',
+            HouseSystem::Alcabitius => 'B',
+            HouseSystem::Morinus => 'M',
+            HouseSystem::Topocentric => 'T',
+            HouseSystem::Meridian => 'X',
+            HouseSystem::Gauquelin => 'G',
+        }
+    }
+
+    /// Whether the cusps exist within the polar circles
+    ///
+    /// Placidus, Koch and the Gauquelin sectors divide the diurnal arcs of
+    /// ecliptic degrees, which are undefined for degrees that never rise or set.
+    pub fn is_defined_in_polar_circle(self) -> bool {
+        !matches!(self, HouseSystem::Placidus | HouseSystem::Koch | HouseSystem::Gauquelin)
+    }
+
+    /// All supported house systems
+    pub fn all() -> &'static [HouseSystem] {
+        &[
+            HouseSystem::Placidus,
+            HouseSystem::Koch,
+            HouseSystem::Equal,
+            HouseSystem::EqualMc,
+            HouseSystem::WholeSign,
+            HouseSystem::Porphyry,
+            HouseSystem::Regiomontanus,
+            HouseSystem::Campanus,
+            HouseSystem::Alcabitius,
+            HouseSystem::Morinus,
+            HouseSystem::Topocentric,
+            HouseSystem::Meridian,
+            HouseSystem::Gauquelin,
+        ]
+    }
+}
+
+/// Sidereal zodiacs, identified by their Swiss Ephemeris `SE_SIDM_*` number
+///
+/// Sidereal longitudes are tropical longitudes less the ayanamsa, the
+/// distance of the sidereal zero point from the vernal equinox.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SiderealMode {
+    /// Fagan/Bradley (0), the western sidereal zodiac
+    #[default]
+    FaganBradley,
+    /// Lahiri (1), the official ayanamsa of the Indian Calendar Reform Committee
+    Lahiri,
+    /// B. V. Raman (3)
+    Raman,
+    /// Krishnamurti (5), of the KP system
+    Krishnamurti,
+    /// True Chitra (27): Spica (Chitra) always at 0° Libra
+    TrueChitra,
+    /// User-defined (255): ayanamsa `ayan_t0` in degrees at Julian day `t0` (TT)
+    User { t0: f64, ayan_t0: f64 },
+}
+
+impl SiderealMode {
+    /// Sidereal mode for a Swiss Ephemeris `SE_SIDM_*` number; `t0` and
+    /// `ayan_t0` are only used by `SE_SIDM_USER`
+    pub fn from_i32(sid_mode: i32, t0: f64, ayan_t0: f64) -> Option<Self> {
+        Some(match sid_mode {
+            SE_SIDM_FAGAN_BRADLEY => SiderealMode::FaganBradley,
+            SE_SIDM_LAHIRI => SiderealMode::Lahiri,
+            SE_SIDM_RAMAN => SiderealMode::Raman,
+            SE_SIDM_KRISHNAMURTI => SiderealMode::Krishnamurti,
+            SE_SIDM_TRUE_CITRA => SiderealMode::TrueChitra,
+            SE_SIDM_USER => SiderealMode::User { t0, ayan_t0 },
+            _ => return None,
+        })
+    }
+
+    /// Swiss Ephemeris `SE_SIDM_*` number
+    pub fn id(self) -> i32 {
+        match self {
+            SiderealMode::FaganBradley => SE_SIDM_FAGAN_BRADLEY,
+            SiderealMode::Lahiri => SE_SIDM_LAHIRI,
+            SiderealMode::Raman => SE_SIDM_RAMAN,
+            SiderealMode::Krishnamurti => SE_SIDM_KRISHNAMURTI,
+            SiderealMode::TrueChitra => SE_SIDM_TRUE_CITRA,
+            SiderealMode::User { .. } => SE_SIDM_USER,
+        }
+    }
+}
+
 /// House cusps result
 #[derive(Debug, Clone, Default)]
 pub struct Houses {
-    /// House cusps in degrees (index 1-12, index 0 unused)
+    /// House cusps in degrees (index 1-12, index 0 unused). For Gauquelin
+    /// sectors these are the Placidus cusps, every third sector cusp.
     pub cusps: [f64; 13],
+    /// Gauquelin sector cusps in degrees (index 1-36, index 0 unused), empty
+    /// for the other systems
+    pub sectors: Vec<f64>,
     /// Ascendant in degrees
     pub ascendant: f64,
     /// Midheaven (MC) in degrees
@@ -142,6 +529,17 @@ pub struct Houses {
     pub armc: f64,
     /// Vertex
     pub vertex: f64,
+    /// Equatorial Ascendant: the ecliptic degree rising at the equator
+    pub equatorial_ascendant: f64,
+    /// Co-Ascendant of Walter Koch
+    pub co_ascendant_koch: f64,
+    /// Co-Ascendant of Michael Munkasey
+    pub co_ascendant_munkasey: f64,
+    /// Polar Ascendant of Michael Munkasey
+    pub polar_ascendant: f64,
+    /// House system the cusps were calculated in: the requested one, or
+    /// Porphyry where it is undefined within the polar circles (see [`calc_houses`])
+    pub system: HouseSystem,
 }
 
 /// Error type
@@ -176,15 +574,211 @@ pub type Result<T> = std::result::Result<T, Error>;
 /// * `speed` - Whether to calculate speed
 ///
 /// # Returns
-/// Position with longitude, latitude, distance, and optionally speeds
+/// Apparent position with longitude, latitude, distance, and optionally
+/// speeds; in the sidereal zodiac when a mode is set with [`set_sid_mode`]
 pub fn calc_ut(jd_ut: f64, planet: Planet, speed: bool) -> Result<Position> {
+    let mut iflag = if speed { SEFLG_SPEED } else { 0 };
+    if get_sid_mode().is_some() {
+        iflag |= SEFLG_SIDEREAL;
+    }
+    calc_ut_flags(jd_ut, planet, iflag)
+}
+
+/// Calculate planet position with Swiss Ephemeris style flags
+///
+/// By default positions are apparent: corrected for light-time, annual
+/// aberration and nutation, referred to the true equinox of date.
+///
+/// # Arguments
+/// * `jd_ut` - Julian day (Universal Time)
+/// * `planet` - Planet identifier
+/// * `iflag` - Combination of `SEFLG_SPEED`, `SEFLG_TRUEPOS` (geometric
+///   position, no light-time or aberration), `SEFLG_NOABERR`, `SEFLG_NONUT`
+///   (mean equinox of date) and `SEFLG_EQUATORIAL` (right ascension and
+///   declination in place of longitude and latitude), `SEFLG_J2000`
+///   (mean equinox and ecliptic of J2000, implies no nutation) and
+///   `SEFLG_TOPOCTR` (seen from the observer given to [`set_topo`]),
+///   `SEFLG_SIDEREAL` (sidereal longitude in the mode of [`set_sid_mode`];
+///   ignored with `SEFLG_EQUATORIAL` and `SEFLG_J2000`). For `SEFLG_XYZ`
+///   use [`calc_ut_xyz`].
+pub fn calc_ut_flags(jd_ut: f64, planet: Planet, iflag: i32) -> Result<Position> {
     // Convert UT to ET (add delta-T)
-    let jd_et = jd_ut + delta_t(jd_ut);
+    calc_at(jd_ut + delta_t(jd_ut), jd_ut, planet, iflag)
+}
+
+/// Calculate planet position for Terrestrial Time (Swiss Ephemeris `swe_calc`)
+///
+/// Same as [`calc_ut_flags`], for callers who already have TT/ET and want to
+/// skip the delta-T step. Universal Time is only needed for the sidereal time
+/// of `SEFLG_TOPOCTR`; it is derived from `jd_et` with [`delta_t`].
+pub fn calc(jd_et: f64, planet: Planet, iflag: i32) -> Result<Position> {
+    calc_at(jd_et, jd_et - delta_t(jd_et), planet, iflag)
+}
+
+/// Apparent position at a moment given in both ET and UT
+fn calc_at(jd_et: f64, jd_ut: f64, planet: Planet, iflag: i32) -> Result<Position> {
+    let speed = iflag & SEFLG_SPEED != 0;
+    let pos = match planet {
+        Planet::Moon => moon::calc_moon(jd_et, iflag)?,
+        Planet::MeanNode => nodes::calc_mean_node(jd_et, speed)?,
+        Planet::TrueNode => nodes::calc_true_node(jd_et, speed)?,
+        Planet::MeanApogee => nodes::calc_mean_apogee(jd_et, speed)?,
+        Planet::OscuApogee => nodes::calc_oscu_apogee(jd_et, speed)?,
+        _ => planets::calc_planet(jd_et, planet, iflag)?,
+    };
 
-    match planet {
-        Planet::Moon => moon::calc_moon(jd_et, speed),
-        Planet::TrueNode => nodes::calc_true_node(jd_et, speed),
-        _ => planets::calc_planet(jd_et, planet, speed),
+    // Points of the geocentric lunar orbit have no parallax to apply
+    to_frame(pos, jd_et, jd_ut, iflag, !planet.is_lunar_point())
+}
+
+/// Apply nutation, topocentric parallax, precession to J2000, the ayanamsa
+/// and the equatorial rotation of `iflag`
+fn to_frame(mut pos: Position, jd_et: f64, jd_ut: f64, iflag: i32, parallax: bool) -> Result<Position> {
+    let j2000 = iflag & SEFLG_J2000 != 0;
+    let mut eps = math::obliquity(jd_et);
+    let mut sidereal = math::sidereal_time(jd_ut) * 15.0;
+    // Nutation in longitude and its speed (degrees)
+    let mut nut_lon = (0.0, 0.0);
+    if iflag & SEFLG_NONUT == 0 && !j2000 {
+        let (dpsi, deps) = nutation::nutation(jd_et);
+        nut_lon.0 = dpsi * RAD_TO_DEG;
+        if iflag & SEFLG_SPEED != 0 {
+            // Nutation in longitude changes by up to 0.001"/day
+            let before = nutation::nutation(jd_et - PLAN_SPEED_INTV).0;
+            let after = nutation::nutation(jd_et + PLAN_SPEED_INTV).0;
+            nut_lon.1 = (after - before) * RAD_TO_DEG / (2.0 * PLAN_SPEED_INTV);
+        }
+        pos.longitude = deg_norm(pos.longitude + nut_lon.0);
+        pos.speed_longitude += nut_lon.1;
+        eps += deps;
+        // Equation of the equinoxes: apparent sidereal time
+        sidereal += dpsi * eps.cos() * RAD_TO_DEG;
+    }
+
+    if iflag & SEFLG_TOPOCTR != 0 && parallax {
+        let observer = get_topo().ok_or_else(|| {
+            Error::CalculationError("SEFLG_TOPOCTR requires an observer, call set_topo first".to_string())
+        })?;
+        pos = topo::topocentric(&pos, &observer, sidereal + observer.longitude, eps);
+    }
+
+    if j2000 {
+        pos = ecliptic_to_j2000(&pos, jd_et, iflag & SEFLG_SPEED != 0);
+        eps = math::obliquity(J2000);
+    } else if iflag & SEFLG_SIDEREAL != 0 && iflag & SEFLG_EQUATORIAL == 0 {
+        // The ayanamsa is counted from the mean equinox: take the nutation out again
+        let mode = get_sid_mode().unwrap_or_default();
+        pos.longitude = deg_norm(pos.longitude - sidereal::ayanamsa(jd_et, mode)? - nut_lon.0);
+        if iflag & SEFLG_SPEED != 0 {
+            pos.speed_longitude -= sidereal::ayanamsa_speed(jd_et, mode)? + nut_lon.1;
+        }
+    }
+
+    if iflag & SEFLG_EQUATORIAL != 0 {
+        pos = ecliptic_to_equatorial(&pos, eps);
+    }
+
+    Ok(pos)
+}
+
+/// Calculate the nodes and apsides of a body's orbit (Swiss Ephemeris `swe_nod_aps_ut`)
+///
+/// Only the Moon is supported. With [`NodApsMethod::Mean`] the points are the
+/// mean node and mean apogee (the same as [`Planet::MeanNode`] and
+/// [`Planet::MeanApogee`]); with [`NodApsMethod::Osculating`] they come from
+/// the osculating orbit (as [`Planet::TrueNode`] and [`Planet::OscuApogee`]).
+/// `SEFLG_SPEED`, `SEFLG_NONUT` and `SEFLG_EQUATORIAL` work as in
+/// [`calc_ut_flags`]; `SEFLG_TOPOCTR` is ignored, the points have no parallax.
+pub fn nod_aps_ut(jd_ut: f64, planet: Planet, iflag: i32, method: NodApsMethod) -> Result<NodAps> {
+    nod_aps_at(jd_ut + delta_t(jd_ut), jd_ut, planet, iflag, method)
+}
+
+/// Calculate the nodes and apsides for Terrestrial Time (Swiss Ephemeris `swe_nod_aps`)
+///
+/// Same as [`nod_aps_ut`] without the delta-T step.
+pub fn nod_aps(jd_et: f64, planet: Planet, iflag: i32, method: NodApsMethod) -> Result<NodAps> {
+    nod_aps_at(jd_et, jd_et - delta_t(jd_et), planet, iflag, method)
+}
+
+fn nod_aps_at(jd_et: f64, jd_ut: f64, planet: Planet, iflag: i32, method: NodApsMethod) -> Result<NodAps> {
+    if planet != Planet::Moon {
+        return Err(Error::InvalidPlanet(planet.id()));
+    }
+
+    let raw = nodes::calc_nod_aps(jd_et, method, iflag & SEFLG_SPEED != 0)?;
+    let frame = |pos: Position| to_frame(pos, jd_et, jd_ut, iflag, false);
+    Ok(NodAps {
+        ascending: frame(raw.ascending)?,
+        descending: frame(raw.descending)?,
+        perigee: frame(raw.perigee)?,
+        apogee: frame(raw.apogee)?,
+    })
+}
+
+/// Calculate right ascension and declination
+///
+/// Same as [`calc_ut_flags`] with `SEFLG_EQUATORIAL`: `longitude` holds the
+/// right ascension and `latitude` the declination (degrees), referred to the
+/// true equator and equinox of date (mean with `SEFLG_NONUT`).
+pub fn calc_ut_equatorial(jd_ut: f64, planet: Planet, iflag: i32) -> Result<Position> {
+    calc_ut_flags(jd_ut, planet, iflag | SEFLG_EQUATORIAL)
+}
+
+/// Calculate a rectangular state vector (Swiss Ephemeris `SEFLG_XYZ`)
+///
+/// Geocentric ecliptic x, y, z in AU and their velocities in AU/day, or
+/// equatorial ones with `SEFLG_EQUATORIAL`. The other flags work as in
+/// [`calc_ut_flags`]; speeds are always calculated.
+pub fn calc_ut_xyz(jd_ut: f64, planet: Planet, iflag: i32) -> Result<StateVector> {
+    calc_ut_flags(jd_ut, planet, (iflag | SEFLG_SPEED) & !SEFLG_XYZ).map(StateVector::from)
+}
+
+/// Rotate an ecliptic position and its speeds onto the equator
+fn ecliptic_to_equatorial(pos: &Position, eps: f64) -> Position {
+    let sv = StateVector::from(*pos);
+    let x = math::coord_trans(&[sv.x, sv.y, sv.z], -eps);
+    let v = math::coord_trans(&[sv.speed_x, sv.speed_y, sv.speed_z], -eps);
+    let pol = math::cart_to_pol_speed(&[x[0], x[1], x[2], v[0], v[1], v[2]]);
+
+    Position {
+        longitude: deg_norm(pol[0] * RAD_TO_DEG),
+        latitude: pol[1] * RAD_TO_DEG,
+        distance: pol[2],
+        speed_longitude: pol[3] * RAD_TO_DEG,
+        speed_latitude: pol[4] * RAD_TO_DEG,
+        speed_distance: pol[5],
+    }
+}
+
+/// Precess an ecliptic position of date and its speeds to the J2000 ecliptic
+///
+/// The speeds include the motion of the frame: the velocity is the symmetric
+/// difference of the precessed positions a [`PLAN_SPEED_INTV`] either side.
+fn ecliptic_to_j2000(pos: &Position, jd: f64, speed: bool) -> Position {
+    let sv = StateVector::from(*pos);
+    let x = [sv.x, sv.y, sv.z];
+    let v = [sv.speed_x, sv.speed_y, sv.speed_z];
+    let h = PLAN_SPEED_INTV;
+    let at = |dt: f64| {
+        let x = [x[0] + v[0] * dt, x[1] + v[1] * dt, x[2] + v[2] * dt];
+        precession::precess_ecliptic(&x, jd + dt, J2000)
+    };
+    let v = if speed {
+        let (before, after) = (at(-h), at(h));
+        [0, 1, 2].map(|i| (after[i] - before[i]) / (2.0 * h))
+    } else {
+        [0.0; 3]
+    };
+    let x = at(0.0);
+    let pol = math::cart_to_pol_speed(&[x[0], x[1], x[2], v[0], v[1], v[2]]);
+
+    Position {
+        longitude: deg_norm(pol[0] * RAD_TO_DEG),
+        latitude: pol[1] * RAD_TO_DEG,
+        distance: pol[2],
+        speed_longitude: pol[3] * RAD_TO_DEG,
+        speed_latitude: pol[4] * RAD_TO_DEG,
+        speed_distance: pol[5],
     }
 }
 
@@ -192,60 +786,132 @@ pub fn calc_ut(jd_ut: f64, planet: Planet, speed: bool) -> Result<Position> {
 ///
 /// # Arguments
 /// * `jd_ut` - Julian day (Universal Time)
-/// * `planet` - Planet identifier (Earth, Mercury through Pluto; not Sun/Moon/TrueNode)
+/// * `planet` - Planet identifier (Earth, Mercury through Pluto; not Sun/Moon or the lunar points)
 /// * `speed` - Whether to calculate speed
 ///
 /// # Returns
 /// Position with heliocentric ecliptic longitude, latitude, distance from Sun, and optionally speed.
-/// Speed is always positive (no retrograde in heliocentric frame).
+/// Speeds in longitude, latitude and distance are symmetric differences of the full model.
 pub fn calc_heliocentric_ut(jd_ut: f64, planet: Planet, speed: bool) -> Result<Position> {
-    let jd_et = jd_ut + delta_t(jd_ut);
+    calc_heliocentric(jd_ut + delta_t(jd_ut), planet, speed)
+}
+
+/// Calculate heliocentric planet position for Terrestrial Time
+///
+/// Same as [`calc_heliocentric_ut`] without the delta-T step.
+pub fn calc_heliocentric(jd_et: f64, planet: Planet, speed: bool) -> Result<Position> {
     planets::calc_heliocentric(jd_et, planet, speed)
 }
 
-/// Calculate house cusps (Placidus system)
+/// Calculate house cusps (Swiss Ephemeris `swe_houses`)
 ///
 /// # Arguments
 /// * `jd_ut` - Julian day (Universal Time)
 /// * `lat` - Geographic latitude in degrees
 /// * `lon` - Geographic longitude in degrees
+/// * `hsys` - House system
 ///
 /// # Returns
 /// House cusps and angles
-pub fn calc_houses(jd_ut: f64, lat: f64, lon: f64) -> Result<Houses> {
-    houses::calc_houses_placidus(jd_ut, lat, lon)
+///
+/// With a mode set by [`set_sid_mode`], the cusps and angles are sidereal
+/// (the ARMC stays a right ascension), and Whole Sign houses start at the
+/// sidereal sign of the Ascendant.
+///
+/// Within the polar circles (|lat| ≥ 90° − obliquity) some ecliptic degrees
+/// never rise or set, and Placidus, Koch and Gauquelin cusps do not exist. As in Swiss
+/// Ephemeris, the cusps are then calculated in the Porphyry system and
+/// [`Houses::system`] reports [`HouseSystem::Porphyry`]. The other quadrant
+/// systems fall back the same way at the times their house circles meet the
+/// ecliptic out of order, and the Ascendant is the one that keeps it east of the MC.
+pub fn calc_houses(jd_ut: f64, lat: f64, lon: f64, hsys: HouseSystem) -> Result<Houses> {
+    houses::calc_houses(jd_ut, lat, lon, hsys)
 }
 
-/// Simple delta-T approximation (TT - UT in days)
-/// Good enough for horoscope accuracy (~1 second)
-pub fn delta_t(jd: f64) -> f64 {
-    // Approximate delta-T in seconds
-    let year = 2000.0 + (jd - J2000) / 365.25;
-
-    let dt_seconds = if year < 1900.0 {
-        // Before 1900: rough polynomial
-        let t = (year - 1820.0) / 100.0;
-        -20.0 + 32.0 * t * t
-    } else if year < 1950.0 {
-        // 1900-1950
-        let t = year - 1900.0;
-        -2.79 + 1.494119 * t - 0.0598939 * t * t + 0.0061966 * t * t * t
-    } else if year < 2005.0 {
-        // 1950-2005
-        let t = year - 2000.0;
-        63.86 + 0.3345 * t - 0.060374 * t * t + 0.0017275 * t * t * t
-    } else if year < 2050.0 {
-        // 2005-2050
-        let t = year - 2000.0;
-        62.92 + 0.32217 * t + 0.005589 * t * t
-    } else {
-        // After 2050: extrapolate
-        let t = (year - 1820.0) / 100.0;
-        -20.0 + 32.0 * t * t
-    };
+/// Calculate house cusps for a given ARMC (Swiss Ephemeris `swe_houses_armc`)
+///
+/// For progressed charts, directions and composite charts, where the houses
+/// belong to an ARMC and obliquity rather than to a moment and place.
+///
+/// # Arguments
+/// * `armc` - Right ascension of the MC in degrees (local sidereal time × 15)
+/// * `lat` - Geographic latitude in degrees
+/// * `eps` - Obliquity of the ecliptic in degrees
+/// * `hsys` - House system
+///
+/// # Returns
+/// House cusps and angles, with the polar fallback of [`calc_houses`]
+pub fn houses_armc(armc: f64, lat: f64, eps: f64, hsys: HouseSystem) -> Result<Houses> {
+    houses::houses_armc(armc, lat, eps, hsys)
+}
+
+/// House position of a body (Swiss Ephemeris `swe_house_pos`)
+///
+/// Unlike comparing longitudes with the cusps, this places a body with
+/// ecliptic latitude on the house circles of the system itself, and returns
+/// the fraction of the house it has crossed.
+///
+/// # Arguments
+/// * `armc` - Right ascension of the MC in degrees
+/// * `lat` - Geographic latitude in degrees
+/// * `eps` - Obliquity of the ecliptic in degrees
+/// * `hsys` - House system
+/// * `lon` - Tropical ecliptic longitude of the body in degrees
+/// * `lat_body` - Ecliptic latitude of the body in degrees
+///
+/// # Returns
+/// House position in [1, 13), e.g. 7.35 for 35% into the seventh house,
+/// or the sector in [1, 37) for [`HouseSystem::Gauquelin`]. Koch, Porphyry
+/// and Topocentric use the longitude only. For Placidus and Gauquelin, a body
+/// that never sets counts as having a diurnal semi-arc of 180° and one that
+/// never rises a diurnal semi-arc of 0°.
+pub fn house_pos(armc: f64, lat: f64, eps: f64, hsys: HouseSystem, lon: f64, lat_body: f64) -> Result<f64> {
+    houses::house_pos(armc, lat, eps, hsys, lon, lat_body)
+}
+
+/// Gauquelin sector of a body (Swiss Ephemeris `swe_gauquelin_sector`)
+///
+/// The sector follows from the fraction of its diurnal or nocturnal
+/// semi-arc the body has covered, sectors 1-18 above the horizon from
+/// rising to setting, 19-36 below it.
+///
+/// # Arguments
+/// * `jd_ut` - Julian day (Universal Time)
+/// * `planet` - Body
+/// * `lat` - Geographic latitude in degrees
+/// * `lon` - Geographic longitude in degrees
+/// * `with_latitude` - Use the ecliptic latitude of the body (Swiss method 0),
+///   or place it on the ecliptic (method 1)
+///
+/// # Returns
+/// Sector in [1, 37), e.g. 9.5 halfway through the sector before culmination
+pub fn gauquelin_sector(jd_ut: f64, planet: Planet, lat: f64, lon: f64, with_latitude: bool) -> Result<f64> {
+    houses::gauquelin_sector(jd_ut, planet, lat, lon, with_latitude)
+}
+
+/// Ayanamsa for Universal Time (Swiss Ephemeris `swe_get_ayanamsa_ut`)
+///
+/// The mean ayanamsa, in degrees, of the mode set with [`set_sid_mode`], or
+/// of Fagan/Bradley when none is set as in Swiss Ephemeris. Sidereal
+/// longitudes are mean tropical longitudes less this value.
+pub fn get_ayanamsa_ut(jd_ut: f64) -> Result<f64> {
+    get_ayanamsa(jd_ut + delta_t(jd_ut))
+}
+
+/// Ayanamsa for Terrestrial Time (Swiss Ephemeris `swe_get_ayanamsa`)
+///
+/// Same as [`get_ayanamsa_ut`] without the delta-T step.
+pub fn get_ayanamsa(jd_et: f64) -> Result<f64> {
+    sidereal::ayanamsa(jd_et, get_sid_mode().unwrap_or_default())
+}
 
-    // Convert seconds to days
-    dt_seconds / 86400.0
+/// Delta-T (TT - UT in days)
+///
+/// Interpolated from observed values between 1620 and 2026, Espenak-Meeus
+/// polynomials outside of that (see [`deltat`]). A value set with
+/// [`set_delta_t_userdef`] takes precedence.
+pub fn delta_t(jd: f64) -> f64 {
+    context::get_delta_t_userdef().unwrap_or_else(|| deltat::delta_t_auto(jd))
 }
 
 #[cfg(test)]
@@ -254,9 +920,15 @@ mod tests {
 
     #[test]
     fn test_planet_enum() {
-        assert_eq!(Planet::Sun as i32, 0);
-        assert_eq!(Planet::Moon as i32, 1);
-        assert_eq!(Planet::TrueNode as i32, 11);
+        assert_eq!(Planet::Sun.id(), 0);
+        assert_eq!(Planet::Moon.id(), 1);
+        assert_eq!(Planet::TrueNode.id(), 11);
+        assert_eq!(Planet::Custom(2).id(), 42);
+        assert_eq!(Planet::from_i32(10), Some(Planet::MeanNode));
+        assert_eq!(Planet::from_i32(12), Some(Planet::MeanApogee));
+        assert_eq!(Planet::from_i32(13), Some(Planet::OscuApogee));
+        assert_eq!(Planet::from_i32(15), Some(Planet::Chiron));
+        assert_eq!(Planet::from_i32(20), Some(Planet::Vesta));
 
         assert_eq!(Planet::from_i32(0), Some(Planet::Sun));
         assert_eq!(Planet::from_i32(99), None);
@@ -269,10 +941,214 @@ mod tests {
         assert!((pos.sign_degree() - 15.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_apparent_flags() {
+        let jd = J2000;
+        let apparent = calc_ut_flags(jd, Planet::Sun, 0).unwrap();
+        let mean = calc_ut_flags(jd, Planet::Sun, SEFLG_NONUT).unwrap();
+        let geometric = calc_ut_flags(jd, Planet::Sun, SEFLG_NONUT | SEFLG_TRUEPOS).unwrap();
+
+        // Nutation in longitude at J2000 is about -13.9", solar aberration about -20.8" near perihelion
+        let nut = math::angle_diff(apparent.longitude, mean.longitude) * 3600.0;
+        let aberr = math::angle_diff(mean.longitude, geometric.longitude) * 3600.0;
+        assert!((nut + 13.9).abs() < 0.3, "nutation {:.2}\"", nut);
+        assert!((aberr + 20.8).abs() < 0.3, "aberration {:.2}\"", aberr);
+    }
+
+    #[test]
+    fn test_j2000_flag() {
+        let jd = julday_greg(2050, 1, 1, 0.0);
+        let mean = calc_ut_flags(jd, Planet::Sun, SEFLG_NONUT | SEFLG_SPEED).unwrap();
+        let j2000 = calc_ut_flags(jd, Planet::Sun, SEFLG_J2000 | SEFLG_SPEED).unwrap();
+
+        // Half a century of general precession, 2514.7"; the ecliptic of date
+        // turns at 0.138"/day with respect to J2000
+        let shift = math::angle_diff(mean.longitude, j2000.longitude) * 3600.0;
+        assert!((shift - 2514.7).abs() < 1.0, "{:.2}\"", shift);
+        let rate = (mean.speed_longitude - j2000.speed_longitude) * 3600.0;
+        assert!((rate - 0.138).abs() < 0.005, "{:.4}\"/day", rate);
+        assert!(j2000.latitude.abs() * 3600.0 > 1.0);
+
+        // Right ascension on the J2000 equator
+        let eq = calc_ut_flags(jd, Planet::Sun, SEFLG_J2000 | SEFLG_EQUATORIAL).unwrap();
+        let expected = ecliptic_to_equatorial(&j2000, math::obliquity(J2000));
+        assert!(math::angle_diff(eq.longitude, expected.longitude).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sidereal() {
+        // Mesha Sankranti: the Sun entered sidereal Aries (Lahiri) on 2024
+        // April 13 at 21:15 IST
+        let jd = julday_greg(2024, 4, 13, 15.75);
+        let tropical = calc_ut(jd, Planet::Sun, true).unwrap();
+        set_sid_mode(Some(SiderealMode::Lahiri));
+        let sun = calc_ut(jd, Planet::Sun, true).unwrap();
+        assert!(math::angle_diff(sun.longitude, 0.0).abs() < 0.02, "{}", sun.longitude);
+
+        // Mean tropical longitude less the ayanamsa; the speed loses 50"/year
+        let mean = calc_ut_flags(jd, Planet::Sun, SEFLG_NONUT | SEFLG_SPEED).unwrap();
+        let ayanamsa = get_ayanamsa_ut(jd).unwrap();
+        assert!(math::angle_diff(sun.longitude, mean.longitude - ayanamsa).abs() < 1e-9);
+        let rate = (mean.speed_longitude - sun.speed_longitude) * 365.25 * 3600.0;
+        assert!((rate - 50.3).abs() < 0.1, "{}", rate);
+
+        // Cusps and angles, but not the ARMC
+        let tropical_houses = {
+            set_sid_mode(None);
+            calc_houses(jd, 28.61, 77.21, HouseSystem::Placidus).unwrap()
+        };
+        set_sid_mode(Some(SiderealMode::Lahiri));
+        let houses = calc_houses(jd, 28.61, 77.21, HouseSystem::Placidus).unwrap();
+        assert_eq!(houses.armc, tropical_houses.armc);
+        for (sidereal, tropical) in houses.cusps[1..].iter().zip(&tropical_houses.cusps[1..]) {
+            assert!(math::angle_diff(*tropical - ayanamsa, *sidereal).abs() < 1e-9);
+        }
+        assert!(math::angle_diff(tropical_houses.ascendant - ayanamsa, houses.ascendant).abs() < 1e-9);
+
+        // Whole Sign houses start at the sign of the sidereal Ascendant
+        let whole = calc_houses(jd, 28.61, 77.21, HouseSystem::WholeSign).unwrap();
+        assert_eq!(whole.cusps[1] % 30.0, 0.0);
+        assert_eq!(whole.cusps[1], (whole.ascendant / 30.0).floor() * 30.0);
+
+        // The flags keep the Swiss convention, Fagan/Bradley by default
+        let flagless = calc_ut_flags(jd, Planet::Sun, 0).unwrap();
+        assert!(math::angle_diff(flagless.longitude, tropical.longitude).abs() < 1e-9);
+        set_sid_mode(None);
+        let fagan = calc_ut_flags(jd, Planet::Sun, SEFLG_SIDEREAL).unwrap();
+        let expected = mean.longitude - sidereal::ayanamsa(jd + delta_t(jd), SiderealMode::FaganBradley).unwrap();
+        assert!(math::angle_diff(fagan.longitude, expected).abs() < 1e-9);
+        assert!(math::angle_diff(calc_ut(jd, Planet::Sun, false).unwrap().longitude, tropical.longitude).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_calc_et_matches_ut() {
+        let jd_ut = julday_greg(2024, 3, 1, 6.0);
+        let jd_et = jd_ut + delta_t(jd_ut);
+        for &planet in &[Planet::Sun, Planet::Moon, Planet::Mars, Planet::TrueNode] {
+            let ut = calc_ut_flags(jd_ut, planet, SEFLG_SPEED).unwrap();
+            let et = calc(jd_et, planet, SEFLG_SPEED).unwrap();
+            assert!((ut.longitude - et.longitude).abs() < 1e-9);
+            assert!((ut.speed_longitude - et.speed_longitude).abs() < 1e-9);
+        }
+        let helio_ut = calc_heliocentric_ut(jd_ut, Planet::Earth, false).unwrap();
+        let helio_et = calc_heliocentric(jd_et, Planet::Earth, false).unwrap();
+        assert!((helio_ut.longitude - helio_et.longitude).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_nod_aps_matches_calc() {
+        let jd = julday_greg(2024, 3, 1, 6.0);
+        for (method, node, apogee) in [
+            (NodApsMethod::Mean, Planet::MeanNode, Planet::MeanApogee),
+            (NodApsMethod::Osculating, Planet::TrueNode, Planet::OscuApogee),
+        ] {
+            let nod_aps = nod_aps_ut(jd, Planet::Moon, SEFLG_SPEED, method).unwrap();
+            let node = calc_ut_flags(jd, node, SEFLG_SPEED).unwrap();
+            let apogee = calc_ut_flags(jd, apogee, SEFLG_SPEED).unwrap();
+            assert!(math::angle_diff(nod_aps.ascending.longitude, node.longitude).abs() < 1e-6);
+            assert!(math::angle_diff(nod_aps.apogee.longitude, apogee.longitude).abs() < 1e-9);
+            assert!((nod_aps.apogee.speed_longitude - apogee.speed_longitude).abs() < 1e-9);
+        }
+        assert!(nod_aps_ut(jd, Planet::Mars, 0, NodApsMethod::Mean).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "full-series")]
+    fn test_equatorial_meeus_example() {
+        // Meeus example 25.b: apparent Sun, 1992 October 13.0 TD
+        let jd_et = julday_greg(1992, 10, 13, 0.0);
+        let jd_ut = jd_et - delta_t(jd_et);
+        let pos = calc_ut_equatorial(jd_ut, Planet::Sun, SEFLG_SPEED).unwrap();
+
+        // 13h13m30.749s, -7°47'01.74"
+        assert!((pos.longitude - 198.378121).abs() < 0.0003, "RA {}", pos.longitude);
+        assert!((pos.latitude + 7.783817).abs() < 0.0003, "Dec {}", pos.latitude);
+        // Sun moves about 1°/day in RA in October, southwards in declination
+        assert!(pos.speed_longitude > 0.9 && pos.speed_longitude < 1.1);
+        assert!(pos.speed_latitude < -0.3 && pos.speed_latitude > -0.5);
+    }
+
+    #[test]
+    fn test_xyz_state_vector() {
+        let jd = julday_greg(2024, 3, 1, 0.0);
+        for &planet in &[Planet::Sun, Planet::Moon, Planet::Mars] {
+            let pos = calc_ut_flags(jd, planet, SEFLG_SPEED).unwrap();
+            let sv = calc_ut_xyz(jd, planet, 0).unwrap();
+            let r = (sv.x * sv.x + sv.y * sv.y + sv.z * sv.z).sqrt();
+            assert!((r - pos.distance).abs() < 1e-12);
+            assert!((sv.z / r - (pos.latitude * DEG_TO_RAD).sin()).abs() < 1e-12);
+
+            // Velocity agrees with the change of position
+            let dt = 0.01;
+            let before = calc_ut_xyz(jd - dt, planet, 0).unwrap();
+            let after = calc_ut_xyz(jd + dt, planet, 0).unwrap();
+            assert!(((after.x - before.x) / (2.0 * dt) - sv.speed_x).abs() < 1e-6 * pos.distance);
+            assert!(((after.y - before.y) / (2.0 * dt) - sv.speed_y).abs() < 1e-6 * pos.distance);
+            assert!(((after.z - before.z) / (2.0 * dt) - sv.speed_z).abs() < 1e-6 * pos.distance);
+        }
+
+        // Equatorial z is the sine of the declination
+        let eq = calc_ut_equatorial(jd, Planet::Sun, 0).unwrap();
+        let sv = calc_ut_xyz(jd, Planet::Sun, SEFLG_EQUATORIAL).unwrap();
+        assert!((sv.z / eq.distance - (eq.latitude * DEG_TO_RAD).sin()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_topocentric_meeus_example() {
+        // Meeus example 40.a: Mars from Palomar, 2003 August 28, 3h17m UT.
+        // Parallax moves it by +1.29s in right ascension and -14.1" in declination.
+        let jd = julday_greg(2003, 8, 28, 3.0 + 17.0 / 60.0);
+        set_topo(-(7.0 + 47.0 / 60.0 + 27.0 / 3600.0) * 15.0, 33.0 + 21.0 / 60.0 + 22.0 / 3600.0, 1706.0);
+
+        let geo = calc_ut_equatorial(jd, Planet::Mars, 0).unwrap();
+        let topo = calc_ut_equatorial(jd, Planet::Mars, SEFLG_TOPOCTR).unwrap();
+        let d_ra = (topo.longitude - geo.longitude) * 240.0;
+        let d_dec = (topo.latitude - geo.latitude) * 3600.0;
+        assert!((d_ra - 1.29).abs() < 0.05, "dRA {:.3}s", d_ra);
+        assert!((d_dec + 14.1).abs() < 0.5, "dDec {:.2}\"", d_dec);
+    }
+
+    #[test]
+    fn test_topocentric_requires_observer() {
+        assert!(calc_ut_flags(J2000, Planet::Moon, SEFLG_TOPOCTR).is_err());
+    }
+
+    #[test]
+    fn test_topocentric_moon_parallax() {
+        // Parallax of the Moon stays below its horizontal parallax of about 1°,
+        // and the Earth's rotation shows up in the topocentric speed
+        set_topo(0.0, 0.0, 0.0);
+        let jd = julday_greg(2024, 1, 15, 0.0);
+        let geo = calc_ut_flags(jd, Planet::Moon, SEFLG_SPEED).unwrap();
+        let mut max_shift = 0.0f64;
+        let mut max_speed_diff = 0.0f64;
+        for hour in 0..24 {
+            let t = jd + hour as f64 / 24.0;
+            let geo_t = calc_ut_flags(t, Planet::Moon, SEFLG_SPEED).unwrap();
+            let topo_t = calc_ut_flags(t, Planet::Moon, SEFLG_SPEED | SEFLG_TOPOCTR).unwrap();
+            max_shift = max_shift.max(math::angle_diff(topo_t.longitude, geo_t.longitude).abs());
+            max_speed_diff = max_speed_diff.max((topo_t.speed_longitude - geo_t.speed_longitude).abs());
+        }
+        let horizontal_parallax = (6378.137 / (geo.distance * AU_KM)).asin() * RAD_TO_DEG;
+        assert!(max_shift > 0.5 * horizontal_parallax && max_shift < 1.05 * horizontal_parallax);
+        // Diurnal swing of the speed is about 2π times the parallax per day
+        assert!(max_speed_diff > 2.0 && max_speed_diff < 7.0, "{}", max_speed_diff);
+    }
+
     #[test]
     fn test_delta_t() {
         // Delta-T around 2000 should be about 63-64 seconds
         let dt = delta_t(J2000);
         assert!((dt * 86400.0 - 63.8).abs() < 1.0);
+
+        // A user-defined value replaces the model for every UT conversion
+        let auto = calc_ut(J2000, Planet::Moon, false).unwrap();
+        set_delta_t_userdef(Some(0.0));
+        assert_eq!(delta_t(J2000), 0.0);
+        let pinned = calc_ut(J2000, Planet::Moon, false).unwrap();
+        set_delta_t_userdef(None);
+        // The Moon moves about 0.5" per second of time
+        let shift = math::angle_diff(auto.longitude, pinned.longitude) * 3600.0;
+        assert!((shift - 63.8 * 0.5).abs() < 5.0, "shift {:.1}\"", shift);
     }
 }
@@ -70,6 +70,40 @@ pub fn cart_to_pol(cart: &[f64; 3]) -> [f64; 3] {
     [lon, lat, dist]
 }
 
+/// Convert polar coordinates and speeds to Cartesian
+/// Input: [lon, lat, dist, lon_speed, lat_speed, dist_speed] (radians, radians/day)
+/// Output: [x, y, z, dx, dy, dz]
+pub fn pol_to_cart_speed(pol: &[f64; 6]) -> [f64; 6] {
+    let (sin_lon, cos_lon) = pol[0].sin_cos();
+    let (sin_lat, cos_lat) = pol[1].sin_cos();
+    let r = pol[2];
+    [
+        r * cos_lat * cos_lon,
+        r * cos_lat * sin_lon,
+        r * sin_lat,
+        pol[5] * cos_lat * cos_lon - r * sin_lat * cos_lon * pol[4] - r * cos_lat * sin_lon * pol[3],
+        pol[5] * cos_lat * sin_lon - r * sin_lat * sin_lon * pol[4] + r * cos_lat * cos_lon * pol[3],
+        pol[5] * sin_lat + r * cos_lat * pol[4],
+    ]
+}
+
+/// Convert Cartesian coordinates and velocities to polar
+/// Input: [x, y, z, dx, dy, dz]
+/// Output: [lon, lat, dist, lon_speed, lat_speed, dist_speed] (radians, radians/day)
+pub fn cart_to_pol_speed(cart: &[f64; 6]) -> [f64; 6] {
+    let pol = cart_to_pol(&[cart[0], cart[1], cart[2]]);
+    let rxy2 = cart[0] * cart[0] + cart[1] * cart[1];
+    let r = pol[2];
+    if r < 1e-16 || rxy2 < 1e-32 {
+        return [pol[0], pol[1], pol[2], 0.0, 0.0, 0.0];
+    }
+
+    let dist_speed = (cart[0] * cart[3] + cart[1] * cart[4] + cart[2] * cart[5]) / r;
+    let lon_speed = (cart[0] * cart[4] - cart[1] * cart[3]) / rxy2;
+    let lat_speed = (cart[5] * r - cart[2] * dist_speed) / (r * rxy2.sqrt());
+    [pol[0], pol[1], pol[2], lon_speed, lat_speed, dist_speed]
+}
+
 /// Coordinate transformation (rotation around X axis)
 /// Used for ecliptic <-> equatorial conversion
 /// eps > 0: equatorial -> ecliptic
@@ -83,6 +117,23 @@ pub fn coord_trans(x_in: &[f64; 3], eps: f64) -> [f64; 3] {
     ]
 }
 
+/// Annual aberration (relativistic)
+/// Input: geocentric position of a body, observer velocity in AU/day
+/// Output: position displaced towards the apex of the observer's motion
+pub fn aberration(x: &[f64; 3], vel: &[f64; 3]) -> [f64; 3] {
+    let ru = (x[0] * x[0] + x[1] * x[1] + x[2] * x[2]).sqrt();
+    let v = [vel[0] / CLIGHT, vel[1] / CLIGHT, vel[2] / CLIGHT];
+    let v2 = v[0] * v[0] + v[1] * v[1] + v[2] * v[2];
+    let b_1 = (1.0 - v2).sqrt();
+    let f1 = (x[0] * v[0] + x[1] * v[1] + x[2] * v[2]) / ru;
+    let f2 = 1.0 + f1 / (1.0 + b_1);
+    [
+        (b_1 * x[0] + f2 * ru * v[0]) / (1.0 + f1),
+        (b_1 * x[1] + f2 * ru * v[1]) / (1.0 + f1),
+        (b_1 * x[2] + f2 * ru * v[2]) / (1.0 + f1),
+    ]
+}
+
 /// Obliquity of the ecliptic (mean, IAU 2006)
 /// Returns obliquity in radians
 pub fn obliquity(jd: f64) -> f64 {
@@ -215,6 +266,17 @@ mod tests {
         assert!((pol[2] - pol2[2]).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_pol_cart_speed_roundtrip() {
+        let pol = [1.0, 0.5, 2.0, 0.01, -0.002, 0.0003];
+        let cart = pol_to_cart_speed(&pol);
+        let pol2 = cart_to_pol_speed(&cart);
+
+        for i in 0..6 {
+            assert!((pol[i] - pol2[i]).abs() < 1e-12);
+        }
+    }
+
     #[test]
     fn test_obliquity() {
         // Obliquity at J2000 should be about 23.439°
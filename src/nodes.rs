@@ -1,72 +1,241 @@
-//! Lunar node calculations
+//! Lunar node and apsis calculations
 //!
-//! Calculates the True (osculating) North Node of the Moon.
+//! Calculates the Mean and True (osculating) North Node of the Moon, and the
+//! mean and osculating apogee (Black Moon Lilith) and perigee. The osculating
+//! points come from the orbit of the geometric Moon at that instant, as in
+//! Swiss Ephemeris.
 
 use crate::constants::*;
 use crate::math::*;
-use crate::{Position, Result};
+use crate::{moon, planets, NodAps, NodApsMethod, Planet, Position, Result};
+
+/// Mean distance of the Moon, given as the distance of the mean node (AU)
+const MOON_MEAN_DIST_AU: f64 = 384400.0 / AU_KM;
+
+/// Mean eccentricity of the lunar orbit
+const MOON_MEAN_ECC: f64 = 0.054900489;
+
+/// Mean inclination of the lunar orbit to the ecliptic (degrees)
+const MOON_MEAN_INCL: f64 = 5.1453964;
 
 /// Calculate True Lunar Node position
 pub fn calc_true_node(jd_et: f64, calc_speed: bool) -> Result<Position> {
+    calc_true_nodes(jd_et, calc_speed).map(|(north, _)| north)
+}
+
+/// Calculate the true (osculating) ascending and descending nodes
+///
+/// The nodes are where the osculating orbit of the Moon, derived from its
+/// geometric position and velocity, crosses the ecliptic of date. Their
+/// distances are those of the osculating ellipse at the nodes.
+pub fn calc_true_nodes(jd_et: f64, calc_speed: bool) -> Result<(Position, Position)> {
+    let nod_aps = calc_nod_aps(jd_et, NodApsMethod::Osculating, calc_speed)?;
+    Ok((nod_aps.ascending, nod_aps.descending))
+}
+
+/// Calculate the mean lunar apogee (mean Black Moon Lilith)
+pub fn calc_mean_apogee(jd_et: f64, calc_speed: bool) -> Result<Position> {
+    calc_nod_aps(jd_et, NodApsMethod::Mean, calc_speed).map(|nod_aps| nod_aps.apogee)
+}
+
+/// Calculate the osculating lunar apogee (true Black Moon Lilith)
+pub fn calc_oscu_apogee(jd_et: f64, calc_speed: bool) -> Result<Position> {
+    calc_nod_aps(jd_et, NodApsMethod::Osculating, calc_speed).map(|nod_aps| nod_aps.apogee)
+}
+
+/// Calculate the nodes and apsides of the lunar orbit (ecliptic of date)
+///
+/// Speeds are symmetric differences, like the planets, except the longitude
+/// speed of the osculating nodes. That one depends on the Moon's acceleration,
+/// which the truncated lunar series get wrong by up to 20%, so it is derived
+/// from the Sun's perturbation of the orbit instead (see [`osculating_node_rate`]).
+pub fn calc_nod_aps(jd_et: f64, method: NodApsMethod, calc_speed: bool) -> Result<NodAps> {
+    let points = |jd: f64| match method {
+        NodApsMethod::Mean => Ok(mean_points(jd)),
+        NodApsMethod::Osculating => osculating_points(jd),
+    };
+
+    let now = points(jd_et)?;
+    let (before, after) = if calc_speed {
+        (points(jd_et - PLAN_SPEED_INTV)?, points(jd_et + PLAN_SPEED_INTV)?)
+    } else {
+        (now, now)
+    };
+
+    let span = 2.0 * PLAN_SPEED_INTV;
+    let position = |i: usize| Position {
+        longitude: now[i][0],
+        latitude: now[i][1],
+        distance: now[i][2],
+        speed_longitude: angle_diff(after[i][0], before[i][0]) / span,
+        speed_latitude: (after[i][1] - before[i][1]) / span,
+        speed_distance: (after[i][2] - before[i][2]) / span,
+    };
+
+    let (mut ascending, mut descending) = (position(0), position(1));
+    if calc_speed && method == NodApsMethod::Osculating {
+        let rate = osculating_node_rate(jd_et)?;
+        ascending.speed_longitude = rate;
+        descending.speed_longitude = rate;
+    }
+
+    Ok(NodAps {
+        ascending,
+        descending,
+        perigee: position(2),
+        apogee: position(3),
+    })
+}
+
+/// Geometric position and velocity of the Moon (AU, AU/day, ecliptic of date)
+fn moon_state(jd_et: f64) -> Result<([f64; 3], [f64; 3])> {
+    let moon = moon::calc_moon(jd_et, SEFLG_SPEED | SEFLG_TRUEPOS)?;
+    let sv = pol_to_cart_speed(&[
+        moon.longitude * DEG_TO_RAD,
+        moon.latitude * DEG_TO_RAD,
+        moon.distance,
+        moon.speed_longitude * DEG_TO_RAD,
+        moon.speed_latitude * DEG_TO_RAD,
+        moon.speed_distance,
+    ]);
+    Ok(([sv[0], sv[1], sv[2]], [sv[3], sv[4], sv[5]]))
+}
+
+/// Longitude speed of the osculating node (degrees/day)
+///
+/// The Earth's attraction keeps the angular momentum `h = r × v` fixed; only
+/// the perturbing acceleration turns it, `dh/dt = r × a`. The Sun's tidal
+/// acceleration accounts for nearly all of it; the planets and the figure of
+/// the Earth add less than 1e-4°/day.
+fn osculating_node_rate(jd_et: f64) -> Result<f64> {
+    let (r, v) = moon_state(jd_et)?;
+    let sun = planets::calc_planet(jd_et, Planet::Sun, SEFLG_TRUEPOS)?;
+    let s = pol_to_cart(&[sun.longitude * DEG_TO_RAD, sun.latitude * DEG_TO_RAD, sun.distance]);
+
+    // Tidal acceleration of the Sun on the Moon relative to the Earth (AU/day²)
+    let gm_sun = KGAUSS * KGAUSS;
+    let d = [s[0] - r[0], s[1] - r[1], s[2] - r[2]];
+    let d3 = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).powf(1.5);
+    let s3 = sun.distance.powi(3);
+    let a = [
+        gm_sun * (d[0] / d3 - s[0] / s3),
+        gm_sun * (d[1] / d3 - s[1] / s3),
+        gm_sun * (d[2] / d3 - s[2] / s3),
+    ];
+
+    let h = cross(&r, &v);
+    let h_dot = cross(&r, &a);
+    // Node longitude atan2(h_x, -h_y), differentiated
+    let rate = (h[0] * h_dot[1] - h[1] * h_dot[0]) / (h[0] * h[0] + h[1] * h[1]);
+
+    Ok(rate * RAD_TO_DEG)
+}
+
+/// Mean orbit: ascending node, descending node, perigee and apogee
+///
+/// Each as [longitude deg, latitude deg, distance AU]. The apsides lie on the
+/// mean orbit, inclined to the ecliptic, and are projected onto it.
+fn mean_points(jd_et: f64) -> [[f64; 3]; 4] {
     let t = (jd_et - J2000) / DAYS_PER_CENTURY;
+    let node = mean_node_longitude(t);
+    let perigee = moon::mean_perigee_longitude(jd_et);
 
-    // Mean longitude of ascending node
-    let omega_mean = deg_norm(125.0445479 - 1934.1362891 * t
-        + 0.0020754 * t * t
-        + t * t * t / 467441.0
-        - t * t * t * t / 60616000.0);
+    let on_orbit = |lon: f64, dist: f64| {
+        // Angle from the node along the orbit, rotated by the inclination
+        let x = pol_to_cart(&[(lon - node) * DEG_TO_RAD, 0.0, dist]);
+        let pol = cart_to_pol(&coord_trans(&x, -MOON_MEAN_INCL * DEG_TO_RAD));
+        [deg_norm(pol[0] * RAD_TO_DEG + node), pol[1] * RAD_TO_DEG, pol[2]]
+    };
+
+    [
+        [node, 0.0, MOON_MEAN_DIST_AU],
+        [deg_norm(node + 180.0), 0.0, MOON_MEAN_DIST_AU],
+        on_orbit(perigee, MOON_MEAN_DIST_AU * (1.0 - MOON_MEAN_ECC)),
+        on_orbit(perigee + 180.0, MOON_MEAN_DIST_AU * (1.0 + MOON_MEAN_ECC)),
+    ]
+}
+
+/// Osculating orbit: ascending node, descending node, perigee and apogee
+///
+/// Each as [longitude deg, latitude deg, distance AU].
+fn osculating_points(jd_et: f64) -> Result<[[f64; 3]; 4]> {
+    let (r, v) = moon_state(jd_et)?;
 
-    // Mean elongation of Moon
-    let d = deg_norm(297.8501921 + 445267.1114034 * t
-        - 0.0018819 * t * t) * DEG_TO_RAD;
+    // Angular momentum, normal to the orbital plane
+    let h = cross(&r, &v);
+    // The ascending node lies along ecliptic pole x h
+    let node_lon = h[0].atan2(-h[1]);
+    let node_dir = [node_lon.cos(), node_lon.sin(), 0.0];
 
-    // Mean anomaly of Sun
-    let m = deg_norm(357.5291092 + 35999.0502909 * t) * DEG_TO_RAD;
+    // Eccentricity vector and semi-latus rectum of the osculating ellipse
+    let mu = GEOGCONST * (1.0 + 1.0 / EARTH_MOON_MRAT) * 86400.0 * 86400.0 / (AU_KM * 1000.0).powi(3);
+    let r_len = (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).sqrt();
+    let vh = cross(&v, &h);
+    let e_vec = [
+        vh[0] / mu - r[0] / r_len,
+        vh[1] / mu - r[1] / r_len,
+        vh[2] / mu - r[2] / r_len,
+    ];
+    let p = (h[0] * h[0] + h[1] * h[1] + h[2] * h[2]) / mu;
+    let e = (e_vec[0] * e_vec[0] + e_vec[1] * e_vec[1] + e_vec[2] * e_vec[2]).sqrt();
+    let e_cos = e_vec[0] * node_dir[0] + e_vec[1] * node_dir[1];
 
-    // Mean anomaly of Moon
-    let mp = deg_norm(134.9633964 + 477198.8675055 * t) * DEG_TO_RAD;
+    // The perigee lies along the eccentricity vector, the apogee opposite
+    let a = p / (1.0 - e * e);
+    let apsis = |scale: f64| {
+        let pol = cart_to_pol(&[e_vec[0] * scale, e_vec[1] * scale, e_vec[2] * scale]);
+        [pol[0] * RAD_TO_DEG, pol[1] * RAD_TO_DEG, pol[2]]
+    };
 
-    // Moon's argument of latitude
-    let f = deg_norm(93.2720950 + 483202.0175233 * t) * DEG_TO_RAD;
+    let north_lon = deg_norm(node_lon * RAD_TO_DEG);
+    Ok([
+        [north_lon, 0.0, p / (1.0 + e_cos)],
+        [deg_norm(north_lon + 180.0), 0.0, p / (1.0 - e_cos)],
+        apsis(a * (1.0 - e) / e),
+        apsis(-a * (1.0 + e) / e),
+    ])
+}
 
-    // Perturbations to the node (in degrees)
-    let delta_omega =
-        -1.4979 * (2.0 * (d - f)).sin()
-        - 0.1500 * m.sin()
-        - 0.1226 * (2.0 * d).sin()
-        + 0.1176 * (2.0 * f).sin()
-        - 0.0801 * (2.0 * (mp - f)).sin();
+/// Cross product of two vectors
+fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
 
-    // True node
-    let omega = deg_norm(omega_mean + delta_omega);
+/// Calculate Mean Lunar Node position
+///
+/// Meeus 47.7 for the longitude; the speed is its analytic derivative.
+pub fn calc_mean_node(jd_et: f64, calc_speed: bool) -> Result<Position> {
+    let t = (jd_et - J2000) / DAYS_PER_CENTURY;
 
-    // Speed calculation
+    let longitude = mean_node_longitude(t);
     let speed = if calc_speed {
-        let dt = 0.1;
-        let pos2 = calc_true_node(jd_et + dt, false)?;
-        angle_diff(pos2.longitude, omega) / dt
+        (-1934.1362891 + 2.0 * 0.0020754 * t + 3.0 * t * t / 467441.0
+            - 4.0 * t * t * t / 60616000.0)
+            / DAYS_PER_CENTURY
     } else {
-        // Mean daily motion of the node (retrograde)
-        -0.0529539
+        0.0
     };
 
     Ok(Position {
-        longitude: omega,
+        longitude,
         latitude: 0.0,
-        distance: 0.0,
+        distance: MOON_MEAN_DIST_AU,
         speed_longitude: speed,
         speed_latitude: 0.0,
         speed_distance: 0.0,
     })
 }
 
-/// Calculate Mean Lunar Node position
-pub fn calc_mean_node(jd_et: f64) -> f64 {
-    let t = (jd_et - J2000) / DAYS_PER_CENTURY;
-
+/// Longitude of the mean node in degrees, `t` in Julian centuries from J2000
+fn mean_node_longitude(t: f64) -> f64 {
     deg_norm(125.0445479 - 1934.1362891 * t
         + 0.0020754 * t * t
-        + t * t * t / 467441.0)
+        + t * t * t / 467441.0
+        - t * t * t * t / 60616000.0)
 }
 
 #[cfg(test)]
@@ -86,10 +255,107 @@ mod tests {
         assert!(pos.speed_longitude < 0.0);
     }
 
+    #[test]
+    fn test_true_nodes_opposite() {
+        let jd = julday_greg(2024, 1, 1, 12.0);
+        let (north, south) = calc_true_nodes(jd, true).unwrap();
+        assert!((angle_diff(south.longitude, north.longitude).abs() - 180.0).abs() < 1e-9);
+        assert!((north.speed_longitude - south.speed_longitude).abs() < 1e-9);
+
+        // Both lie on the osculating ellipse, between perigee and apogee distance
+        for node in [north, south] {
+            assert!(node.distance > 0.0023 && node.distance < 0.0029, "{}", node.distance);
+        }
+
+        // The true node stays within about 2° of the mean node
+        let mean = calc_mean_node(jd, false).unwrap();
+        assert!(angle_diff(north.longitude, mean.longitude).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_osculating_node_rate() {
+        // Integrate the Moon's orbit under the Earth and the Sun for ±0.1 day
+        // (RK4) and difference the node of the integrated orbit
+        let mu = GEOGCONST * (1.0 + 1.0 / EARTH_MOON_MRAT) * 86400.0 * 86400.0 / (AU_KM * 1000.0).powi(3);
+        let accel = |t: f64, r: [f64; 3]| {
+            let sun = planets::calc_planet(t, Planet::Sun, SEFLG_TRUEPOS).unwrap();
+            let s = pol_to_cart(&[sun.longitude * DEG_TO_RAD, sun.latitude * DEG_TO_RAD, sun.distance]);
+            let d = [s[0] - r[0], s[1] - r[1], s[2] - r[2]];
+            let d3 = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).powf(1.5);
+            let r3 = (r[0] * r[0] + r[1] * r[1] + r[2] * r[2]).powf(1.5);
+            let s3 = sun.distance.powi(3);
+            [0, 1, 2].map(|i| -mu * r[i] / r3 + KGAUSS * KGAUSS * (d[i] / d3 - s[i] / s3))
+        };
+        let integrated_node = |jd: f64, span: f64| {
+            let (mut r, mut v) = moon_state(jd).unwrap();
+            let dt = span / 100.0;
+            for k in 0..100 {
+                let t = jd + k as f64 * dt;
+                // Intermediate state: from (r, v) along velocity u and acceleration a
+                let step = |u: [f64; 3], a: [f64; 3], h: f64| {
+                    ([0, 1, 2].map(|i| r[i] + h * u[i]), [0, 1, 2].map(|i| v[i] + h * a[i]))
+                };
+                let a1 = accel(t, r);
+                let (r2, v2) = step(v, a1, 0.5 * dt);
+                let a2 = accel(t + 0.5 * dt, r2);
+                let (r3, v3) = step(v2, a2, 0.5 * dt);
+                let a3 = accel(t + 0.5 * dt, r3);
+                let (r4, v4) = step(v3, a3, dt);
+                let a4 = accel(t + dt, r4);
+                r = [0, 1, 2].map(|i| r[i] + dt / 6.0 * (v[i] + 2.0 * v2[i] + 2.0 * v3[i] + v4[i]));
+                v = [0, 1, 2].map(|i| v[i] + dt / 6.0 * (a1[i] + 2.0 * a2[i] + 2.0 * a3[i] + a4[i]));
+            }
+            let h = cross(&r, &v);
+            h[0].atan2(-h[1]) * RAD_TO_DEG
+        };
+        for (year, month) in [(1925, 1), (2000, 1), (2024, 6), (2035, 1)] {
+            let jd = julday_greg(year, month, 1, 12.0);
+            let numeric = angle_diff(integrated_node(jd, 0.1), integrated_node(jd, -0.1)) / 0.2;
+            let rate = osculating_node_rate(jd).unwrap();
+            assert!((rate - numeric).abs() < 1e-4, "{}-{}: {:.6} vs {:.6}", year, month, rate, numeric);
+        }
+    }
+
+    #[test]
+    fn test_mean_apogee() {
+        // Mean apogee at J2000: perigee 83.353° (Meeus 47) plus 180°, in the
+        // orbit plane; projecting it onto the ecliptic moves it by 0.115°
+        let nod_aps = calc_nod_aps(J2000, NodApsMethod::Mean, true).unwrap();
+        assert!((nod_aps.apogee.longitude - 263.468).abs() < 0.01, "{}", nod_aps.apogee.longitude);
+        assert!((angle_diff(nod_aps.apogee.longitude, nod_aps.perigee.longitude).abs() - 180.0).abs() < 1e-6);
+        assert!(nod_aps.apogee.latitude.abs() <= MOON_MEAN_INCL);
+        assert!((nod_aps.apogee.latitude + nod_aps.perigee.latitude).abs() < 1e-9);
+
+        // The line of apsides advances once in about 8.85 years
+        assert!((nod_aps.apogee.speed_longitude * 365.25 * 8.85 - 360.0).abs() < 5.0);
+        assert!(nod_aps.apogee.distance > nod_aps.perigee.distance);
+    }
+
+    #[test]
+    fn test_oscu_apogee() {
+        let jd = julday_greg(2024, 1, 1, 12.0);
+        let oscu = calc_nod_aps(jd, NodApsMethod::Osculating, false).unwrap();
+        let mean = calc_nod_aps(jd, NodApsMethod::Mean, false).unwrap();
+
+        // Perigee and apogee lie on opposite sides of the Earth
+        assert!((angle_diff(oscu.apogee.longitude, oscu.perigee.longitude).abs() - 180.0).abs() < 1e-6);
+        assert!((oscu.apogee.latitude + oscu.perigee.latitude).abs() < 1e-9);
+        // The osculating apogee swings up to about 30° around the mean one
+        assert!(angle_diff(oscu.apogee.longitude, mean.apogee.longitude).abs() < 35.0);
+        assert!(oscu.apogee.distance > 0.0026 && oscu.apogee.distance < 0.0029, "{}", oscu.apogee.distance);
+        assert!(oscu.perigee.distance > 0.0023 && oscu.perigee.distance < 0.0025, "{}", oscu.perigee.distance);
+    }
+
     #[test]
     fn test_mean_node() {
         // At J2000, mean node should be around 125°
-        let node = calc_mean_node(J2000);
-        assert!((node - 125.0).abs() < 1.0);
+        let node = calc_mean_node(J2000, true).unwrap();
+        assert!((node.longitude - 125.0).abs() < 1.0);
+
+        // One revolution in about 18.6 years
+        assert!((node.speed_longitude * 365.25 * 18.6 + 360.0).abs() < 1.0);
+        let before = calc_mean_node(J2000 - 0.5, false).unwrap();
+        let after = calc_mean_node(J2000 + 0.5, false).unwrap();
+        assert!((angle_diff(after.longitude, before.longitude) - node.speed_longitude).abs() < 1e-9);
     }
 }
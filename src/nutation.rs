@@ -0,0 +1,128 @@
+//! Nutation in longitude and obliquity
+//!
+//! IAU 1980 theory with the 63 terms of Meeus table 22.A, which keeps every
+//! term of 0.0003" or more. It differs from IAU 2000B by about 0.01-0.05",
+//! mostly through the difference between the two models, still far below the
+//! accuracy of the planetary theories.
+
+use crate::constants::*;
+use crate::math::*;
+
+/// Nutation in longitude and obliquity
+///
+/// Returns (delta_psi, delta_eps) in radians.
+pub fn nutation(jd_et: f64) -> (f64, f64) {
+    let t = (jd_et - J2000) / DAYS_PER_CENTURY;
+
+    // Fundamental arguments (degrees)
+    let d = 297.85036 + 445267.111480 * t - 0.0019142 * t * t + t * t * t / 189474.0;
+    let m = 357.52772 + 35999.050340 * t - 0.0001603 * t * t - t * t * t / 300000.0;
+    let mp = 134.96298 + 477198.867398 * t + 0.0086972 * t * t + t * t * t / 56250.0;
+    let f = 93.27191 + 483202.017538 * t - 0.0036825 * t * t + t * t * t / 327270.0;
+    let omega = 125.04452 - 1934.136261 * t + 0.0020708 * t * t + t * t * t / 450000.0;
+
+    let args = [
+        deg_norm(d) * DEG_TO_RAD,
+        deg_norm(m) * DEG_TO_RAD,
+        deg_norm(mp) * DEG_TO_RAD,
+        deg_norm(f) * DEG_TO_RAD,
+        deg_norm(omega) * DEG_TO_RAD,
+    ];
+
+    let mut dpsi = 0.0;
+    let mut deps = 0.0;
+    for term in NUTATION_TERMS {
+        let arg: f64 = (0..5).map(|i| term[i] * args[i]).sum();
+        dpsi += (term[5] + term[6] * t) * arg.sin();
+        deps += (term[7] + term[8] * t) * arg.cos();
+    }
+
+    // Coefficients are in units of 0.0001"
+    (dpsi * 1e-4 * ARCSEC_TO_RAD, deps * 1e-4 * ARCSEC_TO_RAD)
+}
+
+/// Periodic terms of the IAU 1980 nutation (Meeus table 22.A)
+///
+/// Columns: multipliers of D, M, M', F, Omega; longitude coefficient and its
+/// rate per century (sine); obliquity coefficient and its rate (cosine).
+/// Units of 0.0001".
+const NUTATION_TERMS: &[[f64; 9]] = &[
+    [0.0, 0.0, 0.0, 0.0, 1.0, -171996.0, -174.2, 92025.0, 8.9],
+    [-2.0, 0.0, 0.0, 2.0, 2.0, -13187.0, -1.6, 5736.0, -3.1],
+    [0.0, 0.0, 0.0, 2.0, 2.0, -2274.0, -0.2, 977.0, -0.5],
+    [0.0, 0.0, 0.0, 0.0, 2.0, 2062.0, 0.2, -895.0, 0.5],
+    [0.0, 1.0, 0.0, 0.0, 0.0, 1426.0, -3.4, 54.0, -0.1],
+    [0.0, 0.0, 1.0, 0.0, 0.0, 712.0, 0.1, -7.0, 0.0],
+    [-2.0, 1.0, 0.0, 2.0, 2.0, -517.0, 1.2, 224.0, -0.6],
+    [0.0, 0.0, 0.0, 2.0, 1.0, -386.0, -0.4, 200.0, 0.0],
+    [0.0, 0.0, 1.0, 2.0, 2.0, -301.0, 0.0, 129.0, -0.1],
+    [-2.0, -1.0, 0.0, 2.0, 2.0, 217.0, -0.5, -95.0, 0.3],
+    [-2.0, 0.0, 1.0, 0.0, 0.0, -158.0, 0.0, 0.0, 0.0],
+    [-2.0, 0.0, 0.0, 2.0, 1.0, 129.0, 0.1, -70.0, 0.0],
+    [0.0, 0.0, -1.0, 2.0, 2.0, 123.0, 0.0, -53.0, 0.0],
+    [2.0, 0.0, 0.0, 0.0, 0.0, 63.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0, 1.0, 63.0, 0.1, -33.0, 0.0],
+    [2.0, 0.0, -1.0, 2.0, 2.0, -59.0, 0.0, 26.0, 0.0],
+    [0.0, 0.0, -1.0, 0.0, 1.0, -58.0, -0.1, 32.0, 0.0],
+    [0.0, 0.0, 1.0, 2.0, 1.0, -51.0, 0.0, 27.0, 0.0],
+    [-2.0, 0.0, 2.0, 0.0, 0.0, 48.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, -2.0, 2.0, 1.0, 46.0, 0.0, -24.0, 0.0],
+    [2.0, 0.0, 0.0, 2.0, 2.0, -38.0, 0.0, 16.0, 0.0],
+    [0.0, 0.0, 2.0, 2.0, 2.0, -31.0, 0.0, 13.0, 0.0],
+    [0.0, 0.0, 2.0, 0.0, 0.0, 29.0, 0.0, 0.0, 0.0],
+    [-2.0, 0.0, 1.0, 2.0, 2.0, 29.0, 0.0, -12.0, 0.0],
+    [0.0, 0.0, 0.0, 2.0, 0.0, 26.0, 0.0, 0.0, 0.0],
+    [-2.0, 0.0, 0.0, 2.0, 0.0, -22.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, -1.0, 2.0, 1.0, 21.0, 0.0, -10.0, 0.0],
+    [0.0, 2.0, 0.0, 0.0, 0.0, 17.0, -0.1, 0.0, 0.0],
+    [2.0, 0.0, -1.0, 0.0, 1.0, 16.0, 0.0, -8.0, 0.0],
+    [-2.0, 2.0, 0.0, 2.0, 2.0, -16.0, 0.1, 7.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0, 1.0, -15.0, 0.0, 9.0, 0.0],
+    [-2.0, 0.0, 1.0, 0.0, 1.0, -13.0, 0.0, 7.0, 0.0],
+    [0.0, -1.0, 0.0, 0.0, 1.0, -12.0, 0.0, 6.0, 0.0],
+    [0.0, 0.0, 2.0, -2.0, 0.0, 11.0, 0.0, 0.0, 0.0],
+    [2.0, 0.0, -1.0, 2.0, 1.0, -10.0, 0.0, 5.0, 0.0],
+    [2.0, 0.0, 1.0, 2.0, 2.0, -8.0, 0.0, 3.0, 0.0],
+    [0.0, 1.0, 0.0, 2.0, 2.0, 7.0, 0.0, -3.0, 0.0],
+    [-2.0, 1.0, 1.0, 0.0, 0.0, -7.0, 0.0, 0.0, 0.0],
+    [0.0, -1.0, 0.0, 2.0, 2.0, -7.0, 0.0, 3.0, 0.0],
+    [2.0, 0.0, 0.0, 2.0, 1.0, -7.0, 0.0, 3.0, 0.0],
+    [2.0, 0.0, 1.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0],
+    [-2.0, 0.0, 2.0, 2.0, 2.0, 6.0, 0.0, -3.0, 0.0],
+    [-2.0, 0.0, 1.0, 2.0, 1.0, 6.0, 0.0, -3.0, 0.0],
+    [2.0, 0.0, -2.0, 0.0, 1.0, -6.0, 0.0, 3.0, 0.0],
+    [2.0, 0.0, 0.0, 0.0, 1.0, -6.0, 0.0, 3.0, 0.0],
+    [0.0, -1.0, 1.0, 0.0, 0.0, 5.0, 0.0, 0.0, 0.0],
+    [-2.0, -1.0, 0.0, 2.0, 1.0, -5.0, 0.0, 3.0, 0.0],
+    [-2.0, 0.0, 0.0, 0.0, 1.0, -5.0, 0.0, 3.0, 0.0],
+    [0.0, 0.0, 2.0, 2.0, 1.0, -5.0, 0.0, 3.0, 0.0],
+    [-2.0, 0.0, 2.0, 0.0, 1.0, 4.0, 0.0, 0.0, 0.0],
+    [-2.0, 1.0, 0.0, 2.0, 1.0, 4.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, -2.0, 0.0, 4.0, 0.0, 0.0, 0.0],
+    [-1.0, 0.0, 1.0, 0.0, 0.0, -4.0, 0.0, 0.0, 0.0],
+    [-2.0, 1.0, 0.0, 0.0, 0.0, -4.0, 0.0, 0.0, 0.0],
+    [1.0, 0.0, 0.0, 0.0, 0.0, -4.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 2.0, 0.0, 3.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, -2.0, 2.0, 2.0, -3.0, 0.0, 0.0, 0.0],
+    [-1.0, -1.0, 1.0, 0.0, 0.0, -3.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 1.0, 0.0, 0.0, -3.0, 0.0, 0.0, 0.0],
+    [0.0, -1.0, 1.0, 2.0, 2.0, -3.0, 0.0, 0.0, 0.0],
+    [2.0, -1.0, -1.0, 2.0, 2.0, -3.0, 0.0, 0.0, 0.0],
+    [0.0, 0.0, 3.0, 2.0, 2.0, -3.0, 0.0, 0.0, 0.0],
+    [2.0, -1.0, 0.0, 2.0, 2.0, -3.0, 0.0, 0.0, 0.0],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::julian::julday_greg;
+
+    #[test]
+    fn test_nutation_meeus_example() {
+        // Meeus example 22.a: 1987 April 10.0 TD
+        let jd = julday_greg(1987, 4, 10, 0.0);
+        let (dpsi, deps) = nutation(jd);
+        assert!((dpsi / ARCSEC_TO_RAD + 3.788).abs() < 0.001);
+        assert!((deps / ARCSEC_TO_RAD - 9.443).abs() < 0.001);
+    }
+}
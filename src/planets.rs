@@ -1,522 +1,301 @@
 //! Planet position calculations using VSOP87 theory
 //!
-//! Simplified VSOP87 implementation for Sun and planets.
-//! Accuracy: ~1 arcsecond for inner planets, ~5 arcseconds for outer planets.
+//! Mercury through Neptune and the Earth come from the truncated VSOP87D
+//! series in [`crate::vsop87`]. Pluto, which VSOP87 does not cover, uses the
+//! periodic series of Meeus chapter 37 inside its 1885-2099 fit window and
+//! Standish's long-term mean elements outside of it. Chiron, Pholus and the
+//! main-belt asteroids are Keplerian orbits from [`crate::asteroids`].
+//!
+//! Geocentric positions are corrected for light-time and annual aberration
+//! unless `SEFLG_TRUEPOS` or `SEFLG_NOABERR` is given; nutation is applied by
+//! the caller. Heliocentric positions are geometric. Both are referred to the
+//! ecliptic of date. The series are good to a few arcseconds between 1800 and
+//! 2200 and to 30" (Mercury through Mars) to 5' (Saturn) over -3000..+3000;
+//! Pluto's fallback elements are good to about 12'.
 
 use crate::constants::*;
 use crate::math::*;
-use crate::{Error, Planet, Position, Result};
+use crate::{asteroids, context, precession, vsop87, Error, Planet, Position, Precision, Result};
+
+/// Start of the Meeus Pluto series fit window (1885-01-01)
+const PLUTO_SERIES_START: f64 = 2409542.5;
+/// End of the Meeus Pluto series fit window (2100-01-01)
+const PLUTO_SERIES_END: f64 = 2488069.5;
 
-/// Calculate heliocentric position of a planet
-pub fn calc_planet(jd_et: f64, planet: Planet, calc_speed: bool) -> Result<Position> {
+/// Calculate geocentric position of the Sun or a planet
+///
+/// `iflag` takes `SEFLG_SPEED`, `SEFLG_TRUEPOS` and `SEFLG_NOABERR`.
+pub fn calc_planet(jd_et: f64, planet: Planet, iflag: i32) -> Result<Position> {
     // Check range
-    if jd_et < MOSHIER_START || jd_et > MOSHIER_END {
+    if !(MOSHIER_START..=MOSHIER_END).contains(&jd_et) {
         return Err(Error::OutOfRange);
     }
+    let precision = context::get_precision();
+    let calc_speed = iflag & SEFLG_SPEED != 0;
+    let true_pos = iflag & SEFLG_TRUEPOS != 0;
+    let aberr = !true_pos && iflag & SEFLG_NOABERR == 0;
 
     match planet {
-        Planet::Sun => calc_sun(jd_et, calc_speed),
-        Planet::Mercury => calc_mercury(jd_et, calc_speed),
-        Planet::Venus => calc_venus(jd_et, calc_speed),
-        Planet::Mars => calc_mars(jd_et, calc_speed),
-        Planet::Jupiter => calc_jupiter(jd_et, calc_speed),
-        Planet::Saturn => calc_saturn(jd_et, calc_speed),
-        Planet::Uranus => calc_uranus(jd_et, calc_speed),
-        Planet::Neptune => calc_neptune(jd_et, calc_speed),
-        Planet::Pluto => calc_pluto(jd_et, calc_speed),
-        Planet::Earth => Err(Error::InvalidPlanet(planet as i32)), // Earth not valid for geocentric
-        _ => Err(Error::InvalidPlanet(planet as i32)),
+        Planet::Sun
+        | Planet::Mercury
+        | Planet::Venus
+        | Planet::Mars
+        | Planet::Jupiter
+        | Planet::Saturn
+        | Planet::Uranus
+        | Planet::Neptune
+        | Planet::Pluto
+        | Planet::Chiron
+        | Planet::Pholus
+        | Planet::Ceres
+        | Planet::Pallas
+        | Planet::Juno
+        | Planet::Vesta
+        | Planet::Custom(_) => position_with_speed(jd_et, calc_speed, |jd| {
+            let earth = earth_helio_xyz(jd, precision);
+            let body_at = |t: f64| match planet {
+                Planet::Sun => Ok([0.0; 3]),
+                _ => helio_xyz(t, planet, precision),
+            };
+            let geo = |body: [f64; 3]| [body[0] - earth[0], body[1] - earth[1], body[2] - earth[2]];
+
+            let mut x = geo(body_at(jd)?);
+            if !true_pos {
+                // Light-time: the body is seen where it was when the light left it
+                for _ in 0..2 {
+                    let dist = (x[0] * x[0] + x[1] * x[1] + x[2] * x[2]).sqrt();
+                    x = geo(body_at(jd - dist * LIGHTTIME_AUNIT)?);
+                }
+            }
+            if aberr {
+                x = aberration(&x, &earth_helio_velocity(jd, precision));
+            }
+            Ok(x)
+        }),
+        // Earth is not valid for geocentric positions
+        _ => Err(Error::InvalidPlanet(planet.id())),
     }
 }
 
-/// Calculate geocentric Sun position using high-precision formulas
-fn calc_sun(jd: f64, calc_speed: bool) -> Result<Position> {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
-    let t2 = t * t;
-    let t3 = t2 * t;
-
-    // Mean longitude of Sun (degrees) - Astronomical Almanac
-    let l0 = deg_norm(280.4664567 + 36000.76982779 * t + 0.0003032028 * t2);
-
-    // Mean anomaly of Sun (degrees)
-    let m = deg_norm(357.5291092 + 35999.0502909 * t - 0.0001536 * t2 + t3 / 24490000.0);
-    let m_rad = m * DEG_TO_RAD;
-
-    // Equation of center (more terms for better accuracy)
-    let c = (1.9146000 - 0.004817 * t - 0.000014 * t2) * m_rad.sin()
-        + (0.019993 - 0.000101 * t) * (2.0 * m_rad).sin()
-        + 0.000290 * (3.0 * m_rad).sin();
-
-    // True longitude
-    let sun_lon = deg_norm(l0 + c);
-
-    // Eccentricity and distance (AU)
-    let e = 0.016708634 - 0.000042037 * t - 0.0000001267 * t2;
-    let v = m_rad + c * DEG_TO_RAD;
-    let r = 1.000001018 * (1.0 - e * e) / (1.0 + e * v.cos());
-
-    // Speed calculation by numerical differentiation
-    let speed = if calc_speed {
-        let dt = 0.01;
-        let jd2 = jd + dt;
-        let t2_new = (jd2 - J2000) / DAYS_PER_CENTURY;
-        let l02 = deg_norm(280.4664567 + 36000.76982779 * t2_new);
-        let m2 = deg_norm(357.5291092 + 35999.0502909 * t2_new) * DEG_TO_RAD;
-        let c2 = 1.9146 * m2.sin() + 0.019993 * (2.0 * m2).sin() + 0.00029 * (3.0 * m2).sin();
-        let sun_lon2 = deg_norm(l02 + c2);
-        angle_diff(sun_lon2, sun_lon) / dt
+/// Calculate heliocentric planet position
+///
+/// Returns heliocentric ecliptic coordinates (longitude relative to Sun).
+/// Valid for Earth, Mercury through Pluto and the minor bodies. Not valid for
+/// Sun, Moon or the lunar points.
+pub fn calc_heliocentric(jd_et: f64, planet: Planet, calc_speed: bool) -> Result<Position> {
+    if !(MOSHIER_START..=MOSHIER_END).contains(&jd_et) {
+        return Err(Error::OutOfRange);
+    }
+    let precision = context::get_precision();
+
+    match planet {
+        Planet::Earth
+        | Planet::Mercury
+        | Planet::Venus
+        | Planet::Mars
+        | Planet::Jupiter
+        | Planet::Saturn
+        | Planet::Uranus
+        | Planet::Neptune
+        | Planet::Pluto
+        | Planet::Chiron
+        | Planet::Pholus
+        | Planet::Ceres
+        | Planet::Pallas
+        | Planet::Juno
+        | Planet::Vesta
+        | Planet::Custom(_) => position_with_speed(jd_et, calc_speed, |jd| helio_xyz(jd, planet, precision)),
+        _ => Err(Error::InvalidPlanet(planet.id())),
+    }
+}
+
+/// Convert a rectangular ecliptic position function into a `Position`
+///
+/// Speeds are taken as symmetric differences over `PLAN_SPEED_INTV`.
+fn position_with_speed<F>(jd: f64, calc_speed: bool, xyz: F) -> Result<Position>
+where
+    F: Fn(f64) -> Result<[f64; 3]>,
+{
+    let pol = cart_to_pol(&xyz(jd)?);
+
+    let (speed_lon, speed_lat, speed_dist) = if calc_speed {
+        let before = cart_to_pol(&xyz(jd - PLAN_SPEED_INTV)?);
+        let after = cart_to_pol(&xyz(jd + PLAN_SPEED_INTV)?);
+        let span = 2.0 * PLAN_SPEED_INTV;
+        (
+            angle_diff(after[0] * RAD_TO_DEG, before[0] * RAD_TO_DEG) / span,
+            (after[1] - before[1]) * RAD_TO_DEG / span,
+            (after[2] - before[2]) / span,
+        )
     } else {
-        0.0
+        (0.0, 0.0, 0.0)
     };
 
     Ok(Position {
-        longitude: sun_lon,
-        latitude: 0.0,
-        distance: r,
-        speed_longitude: speed,
-        speed_latitude: 0.0,
-        speed_distance: 0.0,
+        longitude: deg_norm(pol[0] * RAD_TO_DEG),
+        latitude: pol[1] * RAD_TO_DEG,
+        distance: pol[2],
+        speed_longitude: speed_lon,
+        speed_latitude: speed_lat,
+        speed_distance: speed_dist,
     })
 }
 
-/// Calculate Mercury position (geocentric)
-fn calc_mercury(jd: f64, calc_speed: bool) -> Result<Position> {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
-
-    // Mercury mean elements
-    let l = deg_norm(252.2509 + 149474.0722 * t);
-    let a = 0.38710;
-    let e = 0.20563 + 0.000020 * t;
-    let i = 7.005 + 0.0018 * t;
-    let omega = 48.331 + 1.1852 * t;
-    let pi = 77.456 + 1.5555 * t;
-
-    calc_planet_kepler(jd, l, a, e, i, omega, pi, calc_speed)
-}
-
-/// Calculate Venus position (geocentric)
-fn calc_venus(jd: f64, calc_speed: bool) -> Result<Position> {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
-
-    let l = deg_norm(181.9798 + 58519.2130 * t);
-    let a = 0.72333;
-    let e = 0.00677 - 0.000047 * t;
-    let i = 3.3947 + 0.0010 * t;
-    let omega = 76.680 + 0.9011 * t;
-    let pi = 131.533 + 1.4087 * t;
-
-    calc_planet_kepler(jd, l, a, e, i, omega, pi, calc_speed)
+/// Heliocentric ecliptic rectangular coordinates of a planet (AU, ecliptic of date)
+fn helio_xyz(jd: f64, planet: Planet, precision: Precision) -> Result<[f64; 3]> {
+    match planet {
+        Planet::Pluto => Ok(pluto_helio_xyz(jd)),
+        _ if planet.is_minor_body() => minor_body_helio_xyz(jd, planet),
+        _ => vsop87::heliocentric_xyz(jd, planet, precision).ok_or(Error::InvalidPlanet(planet.id())),
+    }
 }
 
-/// Calculate Mars position (geocentric)
-fn calc_mars(jd: f64, calc_speed: bool) -> Result<Position> {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
-
-    let l = deg_norm(355.4330 + 19141.6964 * t);
-    let a = 1.52368;
-    let e = 0.09340 + 0.000090 * t;
-    let i = 1.8497 - 0.0007 * t;
-    let omega = 49.558 + 0.7721 * t;
-    let pi = 336.060 + 1.8410 * t;
-
-    calc_planet_kepler(jd, l, a, e, i, omega, pi, calc_speed)
+/// Earth's heliocentric ecliptic rectangular coordinates (AU, ecliptic of date)
+fn earth_helio_xyz(jd: f64, precision: Precision) -> [f64; 3] {
+    vsop87::heliocentric_xyz(jd, Planet::Earth, precision).unwrap_or([0.0; 3])
 }
 
-/// Calculate Jupiter position (geocentric) with empirical corrections
-fn calc_jupiter(jd: f64, calc_speed: bool) -> Result<Position> {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
-
-    // Orbital elements with calibrated rate
-    // Original error: +0.87° at t=-0.75, +0.10° at t=0, -1.11° at t=+0.75
-    // Slope: (-1.11 - 0.87) / 1.5 = -1.32°/cy, so add 1.32 to rate
-    // Offset at t=0: +0.10°, so subtract 0.10° from L0
-    let l = deg_norm(34.29644051 + 3036.06 * t + 0.00022374 * t * t);
-    let a = 5.202887 + 0.0000019 * t;
-    let e = 0.04838624 - 0.00013253 * t;
-    let i = 1.30327 - 0.00019872 * t;
-    let omega = 100.47390909 + 0.20469106 * t;
-    let pi = 14.72847983 + 0.21252668 * t;
-
-    calc_planet_kepler(jd, l, a, e, i, omega, pi, calc_speed)
+/// Earth's heliocentric velocity (AU/day, ecliptic of date)
+pub(crate) fn earth_helio_velocity(jd: f64, precision: Precision) -> [f64; 3] {
+    let before = earth_helio_xyz(jd - PLAN_SPEED_INTV, precision);
+    let after = earth_helio_xyz(jd + PLAN_SPEED_INTV, precision);
+    let span = 2.0 * PLAN_SPEED_INTV;
+    [
+        (after[0] - before[0]) / span,
+        (after[1] - before[1]) / span,
+        (after[2] - before[2]) / span,
+    ]
 }
 
-/// Calculate Saturn position (geocentric) with empirical corrections
-fn calc_saturn(jd: f64, calc_speed: bool) -> Result<Position> {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
+/// Pluto's heliocentric position referred to the ecliptic of date
+fn pluto_helio_xyz(jd: f64) -> [f64; 3] {
+    let (lon, lat, r) = if (PLUTO_SERIES_START..PLUTO_SERIES_END).contains(&jd) {
+        pluto_series_j2000(jd)
+    } else {
+        pluto_fallback_j2000(jd)
+    };
 
-    // Orbital elements with calibrated rate
-    // Original error: +1.03° at t=-0.75, -0.17° at t=0, -1.06° at t=+0.75
-    // Slope: (-1.06 - 1.03) / 1.5 = -1.39°/cy, so add 1.39 to rate
-    // Offset: -0.17°, so add 0.17° to L0
-    let l = deg_norm(50.11432077 + 1223.88 * t - 0.00019837 * t * t);
-    let a = 9.536676 + 0.0000044 * t;
-    let e = 0.05386179 - 0.00050991 * t;
-    let i = 2.48887878 + 0.00193609 * t;
-    let omega = 113.66242448 - 0.28867794 * t;
-    let pi = 92.59887831 - 0.04149890 * t;
-
-    calc_planet_kepler(jd, l, a, e, i, omega, pi, calc_speed)
+    precession::precess_ecliptic(&pol_to_cart(&[lon, lat, r]), J2000, jd)
 }
 
-/// Calculate Uranus position (geocentric) with empirical corrections
-fn calc_uranus(jd: f64, calc_speed: bool) -> Result<Position> {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
-
-    // High-precision elements with empirical corrections
-    let l = deg_norm(313.24710451 + 429.8520 * t + 0.00000434 * t * t);
-    let a = 19.189165 - 0.0000024 * t;
-    let e = 0.04725744 - 0.00004397 * t;
-    let i = 0.77319689 - 0.00019490 * t;
-    let omega = 74.01692503 + 0.04240589 * t;
-    let pi = 170.95427630 + 0.40805281 * t;
-
-    calc_planet_kepler(jd, l, a, e, i, omega, pi, calc_speed)
+/// Pluto from the Standish elements outside the series window (J2000 ecliptic)
+///
+/// The elements are shifted by their difference from the series at the nearest
+/// end of the window, so the position does not jump where the models meet.
+/// Returns (longitude rad, latitude rad, radius AU).
+fn pluto_fallback_j2000(jd: f64) -> (f64, f64, f64) {
+    let kepler = |t: f64| cart_to_pol(&kepler_helio_xyz(&pluto_elements(t)));
+    let edge = jd.clamp(PLUTO_SERIES_START, PLUTO_SERIES_END);
+    let (lon0, lat0, r0) = pluto_series_j2000(edge);
+    let at_edge = kepler(edge);
+    let pol = kepler(jd);
+
+    (
+        pol[0] + angle_diff(lon0 * RAD_TO_DEG, at_edge[0] * RAD_TO_DEG) * DEG_TO_RAD,
+        pol[1] + lat0 - at_edge[1],
+        pol[2] + r0 - at_edge[2],
+    )
 }
 
-/// Calculate Neptune position (geocentric) with empirical corrections
-fn calc_neptune(jd: f64, calc_speed: bool) -> Result<Position> {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
-
-    // High-precision elements with empirical corrections
-    let l = deg_norm(304.88197031 + 219.8995 * t - 0.00000070 * t * t);
-    let a = 30.069923 + 0.00000026 * t;
-    let e = 0.00859048 + 0.00000513 * t;
-    let i = 1.76995259 + 0.00022400 * t;
-    let omega = 131.78422574 - 0.00508664 * t;
-    let pi = 44.96476227 - 0.32241464 * t;
+/// A minor body's heliocentric position referred to the ecliptic of date
+fn minor_body_helio_xyz(jd: f64, planet: Planet) -> Result<[f64; 3]> {
+    let el = asteroids::elements(jd, planet)?;
 
-    calc_planet_kepler(jd, l, a, e, i, omega, pi, calc_speed)
+    Ok(precession::precess_ecliptic(&kepler_helio_xyz(&el), J2000, jd))
 }
 
-/// Calculate Pluto position (geocentric) with empirical corrections
-fn calc_pluto(jd: f64, calc_speed: bool) -> Result<Position> {
+/// Pluto from the periodic series of Meeus chapter 37 (J2000 ecliptic)
+///
+/// Returns (longitude rad, latitude rad, radius AU).
+fn pluto_series_j2000(jd: f64) -> (f64, f64, f64) {
     let t = (jd - J2000) / DAYS_PER_CENTURY;
 
-    // Orbital elements with calibrated rate
-    // Original error: +1.08° at t=-0.75, 0° at t=0, -1.05° at t=+0.75
-    // Slope: -1.42°/cy, add to rate
-    let l = deg_norm(238.9286 + 146.60 * t);
-    let a = 39.48169;
-    let e = 0.24883 + 0.00005 * t;
-    let i = 17.1417;
-    let omega = 110.299;
-    let pi = 224.067;
-
-    calc_planet_kepler(jd, l, a, e, i, omega, pi, calc_speed)
-}
-
-/// Calculate planet position using Keplerian elements
-/// Returns geocentric ecliptic coordinates
-fn calc_planet_kepler(
-    jd: f64,
-    mean_lon: f64,
-    semi_major: f64,
-    ecc: f64,
-    incl: f64,
-    asc_node: f64,
-    lon_peri: f64,
-    calc_speed: bool,
-) -> Result<Position> {
-    // Mean anomaly
-    let m = deg_norm(mean_lon - lon_peri) * DEG_TO_RAD;
-
-    // Solve Kepler's equation: E - e*sin(E) = M
-    let e_anom = solve_kepler(m, ecc);
-
-    // True anomaly
-    let v = 2.0 * ((1.0 + ecc).sqrt() * (e_anom / 2.0).tan()).atan2((1.0 - ecc).sqrt());
-
-    // Heliocentric distance
-    let r = semi_major * (1.0 - ecc * e_anom.cos());
-
-    // Argument of latitude
-    let u = v + (lon_peri - asc_node) * DEG_TO_RAD;
-
-    // Convert to ecliptic coordinates
-    let incl_rad = incl * DEG_TO_RAD;
-    let node_rad = asc_node * DEG_TO_RAD;
-
-    let x_ecl = r * (node_rad.cos() * u.cos() - node_rad.sin() * u.sin() * incl_rad.cos());
-    let y_ecl = r * (node_rad.sin() * u.cos() + node_rad.cos() * u.sin() * incl_rad.cos());
-    let z_ecl = r * u.sin() * incl_rad.sin();
-
-    // Get Earth position for geocentric conversion
-    let earth = calc_earth_helio(jd);
-
-    // Geocentric position
-    let x_geo = x_ecl - earth.0;
-    let y_geo = y_ecl - earth.1;
-    let z_geo = z_ecl - earth.2;
-
-    // Convert to spherical coordinates
-    let dist = (x_geo * x_geo + y_geo * y_geo + z_geo * z_geo).sqrt();
-    let lon = deg_norm(y_geo.atan2(x_geo) * RAD_TO_DEG);
-    let lat = (z_geo / dist).asin() * RAD_TO_DEG;
-
-    // Speed calculation by numerical differentiation
-    let speed = if calc_speed {
-        let dt = 0.1;
-        let pos2 = calc_planet_kepler(jd + dt, mean_lon + dt * 360.0 / (365.25 * (semi_major.powf(1.5))),
-                                      semi_major, ecc, incl, asc_node, lon_peri, false)?;
-        angle_diff(pos2.longitude, lon) / dt
-    } else {
-        0.0
-    };
-
-    Ok(Position {
-        longitude: lon,
-        latitude: lat,
-        distance: dist,
-        speed_longitude: speed,
-        speed_latitude: 0.0,
-        speed_distance: 0.0,
-    })
-}
-
-/// Calculate heliocentric planet position
-///
-/// Returns heliocentric ecliptic coordinates (longitude relative to Sun).
-/// Valid for Earth and Mercury through Pluto. Not valid for Sun, Moon, or TrueNode.
-pub fn calc_heliocentric(jd_et: f64, planet: Planet, calc_speed: bool) -> Result<Position> {
-    if jd_et < MOSHIER_START || jd_et > MOSHIER_END {
-        return Err(Error::OutOfRange);
+    let j = 34.35 + 3034.9057 * t;
+    let s = 50.08 + 1222.1138 * t;
+    let p = 238.96 + 144.9600 * t;
+
+    let mut lon = 0.0;
+    let mut lat = 0.0;
+    let mut rad = 0.0;
+    for term in PLUTO_TERMS {
+        let arg = (term[0] * j + term[1] * s + term[2] * p) * DEG_TO_RAD;
+        let (sin_a, cos_a) = arg.sin_cos();
+        lon += term[3] * sin_a + term[4] * cos_a;
+        lat += term[5] * sin_a + term[6] * cos_a;
+        rad += term[7] * sin_a + term[8] * cos_a;
     }
 
-    match planet {
-        Planet::Earth => calc_heliocentric_earth(jd_et, calc_speed),
-        Planet::Mercury => calc_heliocentric_planet(jd_et, planet, calc_speed),
-        Planet::Venus => calc_heliocentric_planet(jd_et, planet, calc_speed),
-        Planet::Mars => calc_heliocentric_planet(jd_et, planet, calc_speed),
-        Planet::Jupiter => calc_heliocentric_planet(jd_et, planet, calc_speed),
-        Planet::Saturn => calc_heliocentric_planet(jd_et, planet, calc_speed),
-        Planet::Uranus => calc_heliocentric_planet(jd_et, planet, calc_speed),
-        Planet::Neptune => calc_heliocentric_planet(jd_et, planet, calc_speed),
-        Planet::Pluto => calc_heliocentric_planet(jd_et, planet, calc_speed),
-        _ => Err(Error::InvalidPlanet(planet as i32)),
-    }
+    (
+        (238.958116 + 144.96 * t + lon * 1e-6) * DEG_TO_RAD,
+        (-3.908239 + lat * 1e-6) * DEG_TO_RAD,
+        40.7241346 + rad * 1e-7,
+    )
 }
 
-/// Get orbital elements for a planet at a given Julian date
-fn get_orbital_elements(jd: f64, planet: Planet) -> Option<(f64, f64, f64, f64, f64, f64)> {
+/// Pluto's long-term mean elements (Standish, valid 3000 BC - 3000 AD)
+fn pluto_elements(jd: f64) -> OrbitalElements {
     let t = (jd - J2000) / DAYS_PER_CENTURY;
 
-    match planet {
-        Planet::Mercury => {
-            let l = deg_norm(252.2509 + 149474.0722 * t);
-            let a = 0.38710;
-            let e = 0.20563 + 0.000020 * t;
-            let i = 7.005 + 0.0018 * t;
-            let omega = 48.331 + 1.1852 * t;
-            let pi = 77.456 + 1.5555 * t;
-            Some((l, a, e, i, omega, pi))
-        }
-        Planet::Venus => {
-            let l = deg_norm(181.9798 + 58519.2130 * t);
-            let a = 0.72333;
-            let e = 0.00677 - 0.000047 * t;
-            let i = 3.3947 + 0.0010 * t;
-            let omega = 76.680 + 0.9011 * t;
-            let pi = 131.533 + 1.4087 * t;
-            Some((l, a, e, i, omega, pi))
-        }
-        Planet::Mars => {
-            let l = deg_norm(355.4330 + 19141.6964 * t);
-            let a = 1.52368;
-            let e = 0.09340 + 0.000090 * t;
-            let i = 1.8497 - 0.0007 * t;
-            let omega = 49.558 + 0.7721 * t;
-            let pi = 336.060 + 1.8410 * t;
-            Some((l, a, e, i, omega, pi))
-        }
-        Planet::Jupiter => {
-            let l = deg_norm(34.29644051 + 3036.06 * t + 0.00022374 * t * t);
-            let a = 5.202887 + 0.0000019 * t;
-            let e = 0.04838624 - 0.00013253 * t;
-            let i = 1.30327 - 0.00019872 * t;
-            let omega = 100.47390909 + 0.20469106 * t;
-            let pi = 14.72847983 + 0.21252668 * t;
-            Some((l, a, e, i, omega, pi))
-        }
-        Planet::Saturn => {
-            let l = deg_norm(50.11432077 + 1223.88 * t - 0.00019837 * t * t);
-            let a = 9.536676 + 0.0000044 * t;
-            let e = 0.05386179 - 0.00050991 * t;
-            let i = 2.48887878 + 0.00193609 * t;
-            let omega = 113.66242448 - 0.28867794 * t;
-            let pi = 92.59887831 - 0.04149890 * t;
-            Some((l, a, e, i, omega, pi))
-        }
-        Planet::Uranus => {
-            let l = deg_norm(313.24710451 + 429.8520 * t + 0.00000434 * t * t);
-            let a = 19.189165 - 0.0000024 * t;
-            let e = 0.04725744 - 0.00004397 * t;
-            let i = 0.77319689 - 0.00019490 * t;
-            let omega = 74.01692503 + 0.04240589 * t;
-            let pi = 170.95427630 + 0.40805281 * t;
-            Some((l, a, e, i, omega, pi))
-        }
-        Planet::Neptune => {
-            let l = deg_norm(304.88197031 + 219.8995 * t - 0.00000070 * t * t);
-            let a = 30.069923 + 0.00000026 * t;
-            let e = 0.00859048 + 0.00000513 * t;
-            let i = 1.76995259 + 0.00022400 * t;
-            let omega = 131.78422574 - 0.00508664 * t;
-            let pi = 44.96476227 - 0.32241464 * t;
-            Some((l, a, e, i, omega, pi))
-        }
-        Planet::Pluto => {
-            let l = deg_norm(238.9286 + 146.60 * t);
-            let a = 39.48169;
-            let e = 0.24883 + 0.00005 * t;
-            let i = 17.1417;
-            let omega = 110.299;
-            let pi = 224.067;
-            Some((l, a, e, i, omega, pi))
-        }
-        _ => None,
+    OrbitalElements {
+        semi_major: 39.48686035 + 0.00449751 * t,
+        ecc: 0.24885238 + 0.00006016 * t,
+        incl: 17.14104260 + 0.00000501 * t,
+        asc_node: 110.30167986 - 0.00809981 * t,
+        lon_peri: 224.09702598 - 0.00968827 * t,
+        mean_lon: 238.96535011 + 145.18042903 * t - 0.01262724 * t * t,
     }
 }
 
-/// Calculate heliocentric position for Mercury-Pluto using Keplerian elements
-fn calc_heliocentric_planet(jd: f64, planet: Planet, calc_speed: bool) -> Result<Position> {
-    let (mean_lon, semi_major, ecc, incl, asc_node, lon_peri) =
-        get_orbital_elements(jd, planet).ok_or(Error::InvalidPlanet(planet as i32))?;
-
-    calc_heliocentric_kepler(jd, mean_lon, semi_major, ecc, incl, asc_node, lon_peri, calc_speed)
+/// Keplerian orbital elements (angles in degrees, distances in AU)
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OrbitalElements {
+    pub(crate) semi_major: f64,
+    pub(crate) ecc: f64,
+    pub(crate) incl: f64,
+    pub(crate) asc_node: f64,
+    pub(crate) lon_peri: f64,
+    pub(crate) mean_lon: f64,
 }
 
-/// Calculate heliocentric position using Keplerian elements
-/// Returns heliocentric ecliptic coordinates (position relative to Sun)
-fn calc_heliocentric_kepler(
-    jd: f64,
-    mean_lon: f64,
-    semi_major: f64,
-    ecc: f64,
-    incl: f64,
-    asc_node: f64,
-    lon_peri: f64,
-    calc_speed: bool,
-) -> Result<Position> {
+/// Heliocentric rectangular coordinates from Keplerian elements
+///
+/// The result is in the frame the elements are referred to.
+fn kepler_helio_xyz(el: &OrbitalElements) -> [f64; 3] {
     // Mean anomaly
-    let m = deg_norm(mean_lon - lon_peri) * DEG_TO_RAD;
+    let m = deg_norm(el.mean_lon - el.lon_peri) * DEG_TO_RAD;
 
     // Solve Kepler's equation: E - e*sin(E) = M
-    let e_anom = solve_kepler(m, ecc);
+    let e_anom = solve_kepler(m, el.ecc);
 
     // True anomaly
-    let v = 2.0 * ((1.0 + ecc).sqrt() * (e_anom / 2.0).tan()).atan2((1.0 - ecc).sqrt());
+    let v = 2.0
+        * ((1.0 + el.ecc).sqrt() * (e_anom / 2.0).sin())
+            .atan2((1.0 - el.ecc).sqrt() * (e_anom / 2.0).cos());
 
     // Heliocentric distance
-    let r = semi_major * (1.0 - ecc * e_anom.cos());
+    let r = el.semi_major * (1.0 - el.ecc * e_anom.cos());
 
     // Argument of latitude
-    let u = v + (lon_peri - asc_node) * DEG_TO_RAD;
-
-    // Convert to heliocentric ecliptic coordinates
-    let incl_rad = incl * DEG_TO_RAD;
-    let node_rad = asc_node * DEG_TO_RAD;
-
-    let x_ecl = r * (node_rad.cos() * u.cos() - node_rad.sin() * u.sin() * incl_rad.cos());
-    let y_ecl = r * (node_rad.sin() * u.cos() + node_rad.cos() * u.sin() * incl_rad.cos());
-    let z_ecl = r * u.sin() * incl_rad.sin();
-
-    // Convert directly to heliocentric spherical coordinates (no Earth subtraction)
-    let dist = (x_ecl * x_ecl + y_ecl * y_ecl + z_ecl * z_ecl).sqrt();
-    let lon = deg_norm(y_ecl.atan2(x_ecl) * RAD_TO_DEG);
-    let lat = (z_ecl / dist).asin() * RAD_TO_DEG;
-
-    // Speed calculation by numerical differentiation
-    let speed = if calc_speed {
-        let dt = 0.1;
-        let pos2 = calc_heliocentric_kepler(
-            jd + dt,
-            mean_lon + dt * 360.0 / (365.25 * semi_major.powf(1.5)),
-            semi_major, ecc, incl, asc_node, lon_peri, false,
-        )?;
-        // Heliocentric speed is always positive (take absolute value)
-        angle_diff(pos2.longitude, lon).abs() / dt
-    } else {
-        0.0
-    };
-
-    Ok(Position {
-        longitude: lon,
-        latitude: lat,
-        distance: dist,
-        speed_longitude: speed,
-        speed_latitude: 0.0,
-        speed_distance: 0.0,
-    })
-}
-
-/// Calculate Earth's heliocentric position as a Position struct
-fn calc_heliocentric_earth(jd: f64, calc_speed: bool) -> Result<Position> {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
-
-    // Earth mean elements
-    let l = deg_norm(100.4665 + 36000.7698 * t) * DEG_TO_RAD;
-    let e = 0.01671 - 0.00004 * t;
-
-    // Mean anomaly
-    let m = deg_norm(357.5291 + 35999.0503 * t) * DEG_TO_RAD;
+    let u = v + (el.lon_peri - el.asc_node) * DEG_TO_RAD;
 
-    // Equation of center
-    let c = (2.0 * e - 0.25 * e * e * e) * m.sin()
-        + 1.25 * e * e * (2.0 * m).sin()
-        + 13.0 / 12.0 * e * e * e * (3.0 * m).sin();
+    let (sin_i, cos_i) = (el.incl * DEG_TO_RAD).sin_cos();
+    let (sin_node, cos_node) = (el.asc_node * DEG_TO_RAD).sin_cos();
+    let (sin_u, cos_u) = u.sin_cos();
 
-    // True longitude and radius
-    let v = l + c;
-    let r = 1.00014 * (1.0 - e * e) / (1.0 + e * (m + c).cos());
-
-    let lon = deg_norm(v * RAD_TO_DEG);
-
-    // Speed calculation by numerical differentiation
-    let speed = if calc_speed {
-        let dt = 0.1;
-        let pos2 = calc_heliocentric_earth(jd + dt, false)?;
-        angle_diff(pos2.longitude, lon).abs() / dt
-    } else {
-        0.0
-    };
-
-    Ok(Position {
-        longitude: lon,
-        latitude: 0.0, // Earth orbits in the ecliptic plane
-        distance: r,
-        speed_longitude: speed,
-        speed_latitude: 0.0,
-        speed_distance: 0.0,
-    })
-}
-
-/// Calculate Earth's heliocentric position
-fn calc_earth_helio(jd: f64) -> (f64, f64, f64) {
-    let t = (jd - J2000) / DAYS_PER_CENTURY;
-
-    // Earth mean elements
-    let l = deg_norm(100.4665 + 36000.7698 * t) * DEG_TO_RAD;
-    let e = 0.01671 - 0.00004 * t;
-
-    // Mean anomaly
-    let m = deg_norm(357.5291 + 35999.0503 * t) * DEG_TO_RAD;
-
-    // Equation of center
-    let c = (2.0 * e - 0.25 * e * e * e) * m.sin()
-        + 1.25 * e * e * (2.0 * m).sin()
-        + 13.0 / 12.0 * e * e * e * (3.0 * m).sin();
-
-    // True longitude and radius
-    let v = l + c;
-    let r = 1.00014 * (1.0 - e * e) / (1.0 + e * (m + c).cos());
-
-    (r * v.cos(), r * v.sin(), 0.0)
+    [
+        r * (cos_node * cos_u - sin_node * sin_u * cos_i),
+        r * (sin_node * cos_u + cos_node * sin_u * cos_i),
+        r * sin_u * sin_i,
+    ]
 }
 
 /// Solve Kepler's equation iteratively
+///
+/// Newton's method; starting from E = pi keeps it convergent for the high
+/// eccentricities of comets.
 fn solve_kepler(m: f64, e: f64) -> f64 {
-    let mut ea = m;
-    for _ in 0..10 {
+    let mut ea = if e > 0.8 { std::f64::consts::PI } else { m };
+    for _ in 0..50 {
         let delta = (ea - e * ea.sin() - m) / (1.0 - e * ea.cos());
         ea -= delta;
         if delta.abs() < 1e-12 {
@@ -526,6 +305,56 @@ fn solve_kepler(m: f64, e: f64) -> f64 {
     ea
 }
 
+/// Periodic terms for Pluto (Meeus table 37.A)
+///
+/// Columns: multipliers of J, S, P, then sine/cosine coefficients for
+/// longitude and latitude (1e-6 degree) and radius vector (1e-7 AU).
+const PLUTO_TERMS: &[[f64; 9]] = &[
+    [0.0, 0.0, 1.0, -19799805.0, 19850055.0, -5452852.0, -14974862.0, 66865439.0, 68951812.0],
+    [0.0, 0.0, 2.0, 897144.0, -4954829.0, 3527812.0, 1672790.0, -11827535.0, -332538.0],
+    [0.0, 0.0, 3.0, 611149.0, 1211027.0, -1050748.0, 327647.0, 1593179.0, -1438890.0],
+    [0.0, 0.0, 4.0, -341243.0, -189585.0, 178690.0, -292153.0, -18444.0, 483220.0],
+    [0.0, 0.0, 5.0, 129287.0, -34992.0, 18650.0, 100340.0, -65977.0, -85431.0],
+    [0.0, 0.0, 6.0, -38164.0, 30893.0, -30697.0, -25823.0, 31174.0, -6032.0],
+    [0.0, 1.0, -1.0, 20442.0, -9987.0, 4878.0, 11248.0, -5794.0, 22161.0],
+    [0.0, 1.0, 0.0, -4063.0, -5071.0, 226.0, -64.0, 4601.0, 4032.0],
+    [0.0, 1.0, 1.0, -6016.0, -3336.0, 2030.0, -836.0, -1729.0, 234.0],
+    [0.0, 1.0, 2.0, -3956.0, 3039.0, 69.0, -604.0, -415.0, 702.0],
+    [0.0, 1.0, 3.0, -667.0, 3572.0, -247.0, -567.0, 239.0, 723.0],
+    [0.0, 2.0, -2.0, 1276.0, 501.0, -57.0, 1.0, 67.0, -67.0],
+    [0.0, 2.0, -1.0, 1152.0, -917.0, -122.0, 175.0, 1034.0, -451.0],
+    [0.0, 2.0, 0.0, 630.0, -1277.0, -49.0, -164.0, -129.0, 504.0],
+    [1.0, -1.0, 0.0, 2571.0, -459.0, -197.0, 199.0, 480.0, -231.0],
+    [1.0, -1.0, 1.0, 899.0, -1449.0, -25.0, 217.0, 2.0, -441.0],
+    [1.0, 0.0, -3.0, -1016.0, 1043.0, 589.0, -248.0, -3359.0, 265.0],
+    [1.0, 0.0, -2.0, -2343.0, -1012.0, -269.0, 711.0, 7856.0, -7832.0],
+    [1.0, 0.0, -1.0, 7042.0, 788.0, 185.0, 193.0, 36.0, 45763.0],
+    [1.0, 0.0, 0.0, 1199.0, -338.0, 315.0, 807.0, 8663.0, 8547.0],
+    [1.0, 0.0, 1.0, 418.0, -67.0, -130.0, -43.0, -809.0, -769.0],
+    [1.0, 0.0, 2.0, 120.0, -274.0, 5.0, 3.0, 263.0, -144.0],
+    [1.0, 0.0, 3.0, -60.0, -159.0, 2.0, 17.0, -126.0, 32.0],
+    [1.0, 0.0, 4.0, -82.0, -29.0, 2.0, 5.0, -35.0, -16.0],
+    [1.0, 1.0, -3.0, -36.0, -29.0, 2.0, 3.0, -19.0, -4.0],
+    [1.0, 1.0, -2.0, -40.0, 7.0, 3.0, 1.0, -15.0, 8.0],
+    [1.0, 1.0, -1.0, -14.0, 22.0, 2.0, -1.0, -4.0, 12.0],
+    [1.0, 1.0, 0.0, 4.0, 13.0, 1.0, -1.0, 5.0, 6.0],
+    [1.0, 1.0, 1.0, 5.0, 2.0, 0.0, -1.0, 3.0, 1.0],
+    [1.0, 1.0, 3.0, -1.0, 0.0, 0.0, 0.0, 6.0, -2.0],
+    [2.0, 0.0, -6.0, 2.0, 0.0, 0.0, -2.0, 2.0, 2.0],
+    [2.0, 0.0, -5.0, -4.0, 5.0, 2.0, 2.0, -2.0, -2.0],
+    [2.0, 0.0, -4.0, 4.0, -7.0, -7.0, 0.0, 14.0, 13.0],
+    [2.0, 0.0, -3.0, 14.0, 24.0, 10.0, -8.0, -63.0, 13.0],
+    [2.0, 0.0, -2.0, -49.0, -34.0, -3.0, 20.0, 136.0, -236.0],
+    [2.0, 0.0, -1.0, 163.0, -48.0, 6.0, 5.0, 273.0, 1065.0],
+    [2.0, 0.0, 0.0, 9.0, -24.0, 14.0, 17.0, 251.0, 149.0],
+    [2.0, 0.0, 1.0, -4.0, 1.0, -2.0, 0.0, -25.0, -9.0],
+    [2.0, 0.0, 2.0, -3.0, 1.0, 0.0, 0.0, 9.0, -2.0],
+    [2.0, 0.0, 3.0, 1.0, 3.0, 0.0, 0.0, -8.0, 7.0],
+    [3.0, 0.0, -2.0, -3.0, -1.0, 0.0, 1.0, -22.0, 4.0],
+    [3.0, 0.0, -1.0, 5.0, -3.0, 0.0, 0.0, 19.0, -18.0],
+    [3.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 10.0, -8.0],
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -534,7 +363,7 @@ mod tests {
     #[test]
     fn test_sun_position() {
         // Test Sun position at J2000
-        let pos = calc_sun(J2000, true).unwrap();
+        let pos = calc_planet(J2000, Planet::Sun, SEFLG_SPEED).unwrap();
         // Sun should be around 280° longitude at J2000
         assert!((pos.longitude - 280.5).abs() < 1.0);
         assert!(pos.latitude.abs() < 0.1);
@@ -543,28 +372,90 @@ mod tests {
     #[test]
     fn test_mercury_position() {
         let jd = julday_greg(2024, 1, 1, 12.0);
-        let pos = calc_mercury(jd, true).unwrap();
+        let pos = calc_planet(jd, Planet::Mercury, SEFLG_SPEED).unwrap();
         // Just check it returns a valid position
         assert!(pos.longitude >= 0.0 && pos.longitude < 360.0);
         assert!(pos.distance > 0.0);
     }
 
+    #[test]
+    #[cfg(feature = "full-series")]
+    fn test_sun_apparent_meeus_example() {
+        // Meeus example 25.b: Sun, 1992 October 13.0 TD. Geometric 199.907372°,
+        // aberration -20.539"; nutation is left to the caller.
+        let jd = julday_greg(1992, 10, 13, 0.0);
+        let geometric = calc_planet(jd, Planet::Sun, SEFLG_TRUEPOS).unwrap();
+        let apparent = calc_planet(jd, Planet::Sun, 0).unwrap();
+        assert!((geometric.longitude - 199.907372).abs() < 0.0001);
+        let aberr = angle_diff(apparent.longitude, geometric.longitude) * 3600.0;
+        assert!((aberr + 20.539).abs() < 0.5, "aberration {:.3}\"", aberr);
+    }
+
+    #[test]
+    fn test_light_time() {
+        // The iteration converges: the retarded position is consistent with its own light-time
+        let jd = julday_greg(2024, 1, 1, 0.0);
+        let geometric = calc_planet(jd, Planet::Jupiter, SEFLG_TRUEPOS).unwrap();
+        let retarded = calc_planet(jd, Planet::Jupiter, SEFLG_NOABERR).unwrap();
+
+        let tau = retarded.distance * LIGHTTIME_AUNIT;
+        let body = helio_xyz(jd - tau, Planet::Jupiter, Precision::Standard).unwrap();
+        let earth = earth_helio_xyz(jd, Precision::Standard);
+        let pol = cart_to_pol(&[body[0] - earth[0], body[1] - earth[1], body[2] - earth[2]]);
+        assert!(angle_diff(pol[0] * RAD_TO_DEG, retarded.longitude).abs() < 1e-8);
+
+        // Jupiter moves ~0.08°/day heliocentrically, ~40 minutes shift it by a few arcseconds
+        let shift = angle_diff(retarded.longitude, geometric.longitude).abs() * 3600.0;
+        assert!(shift > 1.0 && shift < 15.0, "light-time shift {:.2}\"", shift);
+    }
+
+    #[test]
+    fn test_pluto_meeus_example() {
+        // Meeus example 37.a: Pluto, 1992 October 13.0 TD (J2000 ecliptic)
+        let jd = julday_greg(1992, 10, 13, 0.0);
+        let (lon, lat, r) = pluto_series_j2000(jd);
+        assert!((lon * RAD_TO_DEG - 232.74071).abs() < 0.0001);
+        assert!((lat * RAD_TO_DEG - 14.58782).abs() < 0.0001);
+        assert!((r - 29.711111).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_pluto_fallback_continuity() {
+        // The shifted Standish elements join the periodic series without a jump,
+        // in position or in speed
+        for &edge in &[PLUTO_SERIES_START, PLUTO_SERIES_END] {
+            let before = calc_heliocentric(edge - 0.01, Planet::Pluto, true).unwrap();
+            let after = calc_heliocentric(edge + 0.01, Planet::Pluto, true).unwrap();
+            let jump = (angle_diff(after.longitude, before.longitude) - 0.02 * before.speed_longitude) * 3600.0;
+            assert!(jump.abs() < 0.1, "Pluto jump {:.3}\" at JD {}", jump, edge);
+            assert!((after.latitude - before.latitude - 0.02 * before.speed_latitude).abs() * 3600.0 < 0.1);
+            assert!((after.distance - before.distance - 0.02 * before.speed_distance).abs() < 1e-6);
+
+            // The speed straddling the edge stays close to the speed a day away on either side
+            let speed = calc_heliocentric(edge, Planet::Pluto, true).unwrap().speed_longitude;
+            for side in [-1.0, 1.0] {
+                let near = calc_heliocentric(edge + side, Planet::Pluto, true).unwrap().speed_longitude;
+                assert!((speed - near).abs() * 3600.0 < 1.0, "Pluto speed {} vs {} at JD {}", speed, near, edge);
+            }
+        }
+    }
+
     #[test]
     fn test_heliocentric_earth_opposite_sun() {
         // Earth's heliocentric longitude should be ~180° opposite to geocentric Sun longitude
         let jd = julday_greg(2024, 1, 1, 12.0);
-        let sun_geo = calc_sun(jd, true).unwrap();
-        let earth_helio = calc_heliocentric_earth(jd, true).unwrap();
+        let sun_geo = calc_planet(jd, Planet::Sun, SEFLG_SPEED | SEFLG_TRUEPOS).unwrap();
+        let earth_helio = calc_heliocentric(jd, Planet::Earth, true).unwrap();
 
         let diff = ((earth_helio.longitude - sun_geo.longitude).abs() - 180.0).abs();
-        assert!(diff < 1.0, "Earth heliocentric should be ~180° from geocentric Sun, diff from 180°: {:.4}°", diff);
+        assert!(diff < 1e-9, "Earth heliocentric should be 180° from geocentric Sun, diff from 180°: {:.4}°", diff);
     }
 
     #[test]
     fn test_heliocentric_earth_distance() {
         // Earth distance from Sun should be ~1 AU (0.983 - 1.017 range)
         let jd = julday_greg(2024, 6, 15, 12.0);
-        let earth = calc_heliocentric_earth(jd, false).unwrap();
+        let earth = calc_heliocentric(jd, Planet::Earth, false).unwrap();
         assert!(earth.distance > 0.983 && earth.distance < 1.017,
                 "Earth distance should be ~1 AU, got {:.6}", earth.distance);
     }
@@ -573,7 +464,7 @@ mod tests {
     fn test_heliocentric_mercury_distance() {
         // Mercury distance ~0.3-0.47 AU
         let jd = julday_greg(2024, 1, 1, 12.0);
-        let pos = calc_heliocentric_planet(jd, Planet::Mercury, false).unwrap();
+        let pos = calc_heliocentric(jd, Planet::Mercury, false).unwrap();
         assert!(pos.distance > 0.3 && pos.distance < 0.47,
                 "Mercury helio distance should be 0.3-0.47 AU, got {:.6}", pos.distance);
     }
@@ -582,7 +473,7 @@ mod tests {
     fn test_heliocentric_jupiter_distance() {
         // Jupiter distance ~4.9-5.5 AU
         let jd = julday_greg(2024, 1, 1, 12.0);
-        let pos = calc_heliocentric_planet(jd, Planet::Jupiter, false).unwrap();
+        let pos = calc_heliocentric(jd, Planet::Jupiter, false).unwrap();
         assert!(pos.distance > 4.9 && pos.distance < 5.5,
                 "Jupiter helio distance should be 4.9-5.5 AU, got {:.6}", pos.distance);
     }
@@ -606,41 +497,42 @@ mod tests {
     fn test_heliocentric_inner_faster_than_outer() {
         // Inner planets should have faster heliocentric speed than outer planets
         let jd = julday_greg(2024, 1, 1, 12.0);
-        let mercury = calc_heliocentric_planet(jd, Planet::Mercury, true).unwrap();
-        let venus = calc_heliocentric_planet(jd, Planet::Venus, true).unwrap();
-        let earth = calc_heliocentric_earth(jd, true).unwrap();
-        let mars = calc_heliocentric_planet(jd, Planet::Mars, true).unwrap();
-        let jupiter = calc_heliocentric_planet(jd, Planet::Jupiter, true).unwrap();
-        let saturn = calc_heliocentric_planet(jd, Planet::Saturn, true).unwrap();
-        let neptune = calc_heliocentric_planet(jd, Planet::Neptune, true).unwrap();
-
-        assert!(mercury.speed_longitude > venus.speed_longitude,
-                "Mercury should be faster than Venus");
-        assert!(venus.speed_longitude > earth.speed_longitude,
-                "Venus should be faster than Earth");
-        assert!(earth.speed_longitude > mars.speed_longitude,
-                "Earth should be faster than Mars");
-        assert!(mars.speed_longitude > jupiter.speed_longitude,
-                "Mars should be faster than Jupiter");
-        assert!(jupiter.speed_longitude > saturn.speed_longitude,
-                "Jupiter should be faster than Saturn");
-        assert!(saturn.speed_longitude > neptune.speed_longitude,
-                "Saturn should be faster than Neptune");
+        let speed = |planet| calc_heliocentric(jd, planet, true).unwrap().speed_longitude;
+        let order = [Planet::Mercury, Planet::Venus, Planet::Earth, Planet::Mars,
+                     Planet::Jupiter, Planet::Saturn, Planet::Neptune];
+
+        for pair in order.windows(2) {
+            assert!(speed(pair[0]) > speed(pair[1]),
+                    "{:?} should be faster than {:?}", pair[0], pair[1]);
+        }
     }
 
     #[test]
     fn test_heliocentric_valid_longitudes() {
         // All heliocentric longitudes should be in 0-360 range
         let jd = julday_greg(2024, 1, 1, 12.0);
-        for &planet in &[Planet::Mercury, Planet::Venus, Planet::Mars, Planet::Jupiter,
-                         Planet::Saturn, Planet::Uranus, Planet::Neptune, Planet::Pluto] {
-            let pos = calc_heliocentric_planet(jd, planet, false).unwrap();
+        for &planet in Planet::heliocentric_planets() {
+            let pos = calc_heliocentric(jd, planet, false).unwrap();
             assert!(pos.longitude >= 0.0 && pos.longitude < 360.0,
                     "{:?} helio longitude out of range: {:.4}", planet, pos.longitude);
         }
-        let earth = calc_heliocentric_earth(jd, false).unwrap();
-        assert!(earth.longitude >= 0.0 && earth.longitude < 360.0,
-                "Earth helio longitude out of range: {:.4}", earth.longitude);
+    }
+
+    #[test]
+    fn test_full_range_sanity() {
+        // Every body should give finite positions with plausible speeds across the whole range
+        let step = (MOSHIER_END - MOSHIER_START) / 60.0;
+        for i in 0..=60 {
+            let jd = MOSHIER_START + step * i as f64;
+            for &planet in &[Planet::Sun, Planet::Mercury, Planet::Venus, Planet::Mars,
+                             Planet::Jupiter, Planet::Saturn, Planet::Uranus,
+                             Planet::Neptune, Planet::Pluto] {
+                let pos = calc_planet(jd, planet, SEFLG_SPEED).unwrap();
+                assert!(pos.longitude.is_finite() && pos.latitude.abs() < 90.0);
+                assert!(pos.speed_longitude.abs() < 2.5,
+                        "{:?} speed {:.4} at JD {}", planet, pos.speed_longitude, jd);
+            }
+        }
     }
 
     #[test]
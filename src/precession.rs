@@ -0,0 +1,226 @@
+//! Precession between arbitrary epochs
+//!
+//! Long-term precession of Vondrák, Capitaine and Wallace (2011, A&A 534,
+//! A22). The ecliptic and equator poles are a cubic plus periodic terms, valid
+//! for ±200 000 years around J2000. Within a few centuries of J2000 the model
+//! agrees with IAU 2006 precession to a few milliarcseconds and within 0.03"
+//! a millennium away; beyond that the IAU 2006 polynomials drift off while
+//! this model remains valid over the whole range of the ephemeris.
+//!
+//! Frame bias between ICRS and the J2000 mean equator (about 0.02") is
+//! neglected, as everywhere else in this crate.
+
+use crate::constants::*;
+use crate::math::*;
+
+/// 3×3 rotation matrix, row-major
+pub type Matrix = [[f64; 3]; 3];
+
+/// Obliquity of the ecliptic at J2000 used by the model (arcsec)
+const EPS0: f64 = 84381.406;
+
+/// Ecliptic pole: polynomials for P_A and Q_A (arcsec)
+const PQPOL: [[f64; 4]; 2] = [
+    [5851.607687, -0.1189000, -0.00028913, 0.000000101],
+    [-1600.886300, 1.1689818, -0.00000020, -0.000000437],
+];
+
+/// Ecliptic pole: periodic terms
+///
+/// Columns: period (centuries), then P_A and Q_A cosine and sine
+/// amplitudes (arcsec) in the order P cos, Q cos, P sin, Q sin.
+const PQPER: [[f64; 5]; 8] = [
+    [708.15, -5486.751211, -684.661560, 667.666730, -5523.863691],
+    [2309.00, -17.127623, 2446.283880, -2354.886252, -549.747450],
+    [1620.00, -617.517403, 399.671049, -428.152441, -310.998056],
+    [492.20, 413.442940, -356.652376, 376.202861, 421.535876],
+    [1183.00, 78.614193, -186.387003, 184.778874, -36.776172],
+    [622.00, -180.732815, -316.800070, 335.321713, -145.278396],
+    [882.00, -87.676083, 198.296701, -185.138669, -34.744450],
+    [547.00, 46.140315, 101.135679, -120.972830, 22.885731],
+];
+
+/// Equator pole: polynomials for X_A and Y_A (arcsec)
+const XYPOL: [[f64; 4]; 2] = [
+    [5453.282155, 0.4252841, -0.00037173, -0.000000152],
+    [-73750.930350, -0.7675452, -0.00018725, 0.000000231],
+];
+
+/// Equator pole: periodic terms, columns as in [`PQPER`] for X_A and Y_A
+const XYPER: [[f64; 5]; 14] = [
+    [256.75, -819.940624, 75004.344875, 81491.287984, 1558.515853],
+    [708.15, -8444.676815, 624.033993, 787.163481, 7774.939698],
+    [274.20, 2600.009459, 1251.136893, 1251.296102, -2219.534038],
+    [241.45, 2755.175630, -1102.212834, -1257.950837, -2523.969396],
+    [2309.00, -167.659835, -2660.664980, -2966.799730, 247.850422],
+    [492.20, 871.855056, 699.291817, 639.744522, -846.485643],
+    [396.10, 44.769698, 153.167220, 131.600209, -1393.124055],
+    [288.90, -512.313065, -950.865637, -445.040117, 368.526116],
+    [231.10, -819.415595, 499.754645, 584.522874, 749.045012],
+    [1610.00, -538.071099, -145.188210, -89.756563, 444.704518],
+    [620.00, -189.793622, 558.116553, 524.429630, 235.934465],
+    [157.87, -402.922932, -23.923029, -13.549067, 374.049623],
+    [220.30, 179.516345, -165.405086, -210.157124, -171.330180],
+    [1200.00, -9.814756, 9.344131, -44.919798, -22.899655],
+];
+
+/// Evaluate a pair of pole coordinates (radians) from their series
+fn pole_series(t: f64, poly: &[[f64; 4]; 2], periodic: &[[f64; 5]]) -> (f64, f64) {
+    let (mut a, mut b) = (0.0, 0.0);
+    for term in periodic {
+        let (sin_w, cos_w) = (TWOPI * t / term[0]).sin_cos();
+        a += term[1] * cos_w + term[3] * sin_w;
+        b += term[2] * cos_w + term[4] * sin_w;
+    }
+    let mut w = 1.0;
+    for (ca, cb) in poly[0].iter().zip(&poly[1]) {
+        a += ca * w;
+        b += cb * w;
+        w *= t;
+    }
+    (a * ARCSEC_TO_RAD, b * ARCSEC_TO_RAD)
+}
+
+/// Unit vector of the ecliptic pole of date in the J2000 equatorial frame
+fn ecliptic_pole(jd: f64) -> [f64; 3] {
+    let (p, q) = pole_series((jd - J2000) / DAYS_PER_CENTURY, &PQPOL, &PQPER);
+    let z = (1.0 - p * p - q * q).max(0.0).sqrt();
+    let (sin_eps, cos_eps) = (EPS0 * ARCSEC_TO_RAD).sin_cos();
+    [p, -q * cos_eps - z * sin_eps, -q * sin_eps + z * cos_eps]
+}
+
+/// Unit vector of the mean equator pole of date in the J2000 equatorial frame
+fn equator_pole(jd: f64) -> [f64; 3] {
+    let (x, y) = pole_series((jd - J2000) / DAYS_PER_CENTURY, &XYPOL, &XYPER);
+    [x, y, (1.0 - x * x - y * y).max(0.0).sqrt()]
+}
+
+fn cross(a: &[f64; 3], b: &[f64; 3]) -> [f64; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+/// Rotation matrix with `pole` as the z axis and the mean equinox of date,
+/// the ascending node of the ecliptic on the equator, as the x axis
+fn frame(pole: [f64; 3], jd: f64) -> Matrix {
+    let node = cross(&equator_pole(jd), &ecliptic_pole(jd));
+    let len = (node[0] * node[0] + node[1] * node[1] + node[2] * node[2]).sqrt();
+    let x = [node[0] / len, node[1] / len, node[2] / len];
+    [x, cross(&pole, &x), pole]
+}
+
+/// Precession matrix from the J2000 mean equator to the mean equator of `jd`
+pub fn equatorial_matrix(jd: f64) -> Matrix {
+    frame(equator_pole(jd), jd)
+}
+
+/// Rotation from the J2000 mean equator to the mean ecliptic and equinox of `jd`
+pub fn ecliptic_matrix(jd: f64) -> Matrix {
+    frame(ecliptic_pole(jd), jd)
+}
+
+fn mul(m: &Matrix, x: &[f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * x[0] + m[0][1] * x[1] + m[0][2] * x[2],
+        m[1][0] * x[0] + m[1][1] * x[1] + m[1][2] * x[2],
+        m[2][0] * x[0] + m[2][1] * x[1] + m[2][2] * x[2],
+    ]
+}
+
+fn mul_transposed(m: &Matrix, x: &[f64; 3]) -> [f64; 3] {
+    [
+        m[0][0] * x[0] + m[1][0] * x[1] + m[2][0] * x[2],
+        m[0][1] * x[0] + m[1][1] * x[1] + m[2][1] * x[2],
+        m[0][2] * x[0] + m[1][2] * x[1] + m[2][2] * x[2],
+    ]
+}
+
+/// Precess a rectangular equatorial vector from the mean equator and equinox
+/// of `jd_from` to those of `jd_to`
+pub fn precess_equatorial(x: &[f64; 3], jd_from: f64, jd_to: f64) -> [f64; 3] {
+    mul(&equatorial_matrix(jd_to), &mul_transposed(&equatorial_matrix(jd_from), x))
+}
+
+/// Precess a rectangular ecliptic vector from the mean ecliptic and equinox
+/// of `jd_from` to those of `jd_to`
+pub fn precess_ecliptic(x: &[f64; 3], jd_from: f64, jd_to: f64) -> [f64; 3] {
+    mul(&ecliptic_matrix(jd_to), &mul_transposed(&ecliptic_matrix(jd_from), x))
+}
+
+/// Ecliptic of date from a J2000 equatorial vector, as used for star catalogues
+pub fn equatorial_j2000_to_ecliptic(x: &[f64; 3], jd: f64) -> [f64; 3] {
+    mul(&ecliptic_matrix(jd), x)
+}
+
+/// Precess ecliptic longitude and latitude (degrees) between two epochs
+pub fn precess_lon_lat(lon: f64, lat: f64, jd_from: f64, jd_to: f64) -> (f64, f64) {
+    let x = pol_to_cart(&[lon * DEG_TO_RAD, lat * DEG_TO_RAD, 1.0]);
+    let pol = cart_to_pol(&precess_ecliptic(&x, jd_from, jd_to));
+    (deg_norm(pol[0] * RAD_TO_DEG), pol[1] * RAD_TO_DEG)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Julian day of a Julian epoch
+    fn epoch(year: f64) -> f64 {
+        J2000 + (year - 2000.0) * 365.25
+    }
+
+    #[test]
+    fn test_poles() {
+        // Reference values of the SOFA/ERFA test suite (eraLtpecl, eraLtpequ)
+        let pecl = ecliptic_pole(epoch(-1500.0));
+        let expected = [0.4768625676477096525e-3, -0.4052259533091875112, 0.9142164401096448012];
+        for i in 0..3 {
+            assert!((pecl[i] - expected[i]).abs() < 1e-14, "{:?}", pecl);
+        }
+        let pequ = equator_pole(epoch(-2500.0));
+        let expected = [-0.3586652560237326659, -0.1996978910771128475, 0.9118552442250819624];
+        for i in 0..3 {
+            assert!((pequ[i] - expected[i]).abs() < 1e-14, "{:?}", pequ);
+        }
+    }
+
+    #[test]
+    fn test_obliquity() {
+        // The angle between the poles is the mean obliquity of date; the IAU 2006
+        // polynomial drifts away from it further than a millennium from J2000
+        for &year in &[1000.0, 1900.0, 2000.0, 2100.0, 3000.0] {
+            let jd = epoch(year);
+            let (a, b) = (ecliptic_pole(jd), equator_pole(jd));
+            let eps = (a[0] * b[0] + a[1] * b[1] + a[2] * b[2]).acos();
+            assert!(((eps - obliquity(jd)) / ARCSEC_TO_RAD).abs() < 0.05, "{}: {}", year, eps);
+        }
+    }
+
+    #[test]
+    fn test_precession_rate() {
+        // General precession in longitude over the 21st century, IAU 2006
+        // p_A = 5028.796195" T + 1.1054348" T²
+        let (lon, lat) = precess_lon_lat(0.0, 0.0, J2000, J2000 + DAYS_PER_CENTURY);
+        assert!((lon * 3600.0 - 5029.9016).abs() < 0.05, "{}", lon * 3600.0);
+        // The J2000 equinox leaves the moving ecliptic by about 4"
+        assert!(lat.abs() * 3600.0 < 5.0, "{}", lat * 3600.0);
+
+        // Meeus example 21.b: θ Persei, J2000 → 2028 Nov 13.19 TD (RA 41.547214°, Dec 49.348483°)
+        let jd = 2462088.69;
+        let x = pol_to_cart(&[41.054063 * DEG_TO_RAD, 49.227750 * DEG_TO_RAD, 1.0]);
+        let pol = cart_to_pol(&precess_equatorial(&x, J2000, jd));
+        assert!((pol[0] * RAD_TO_DEG - 41.547214).abs() * 3600.0 < 0.5, "{}", pol[0] * RAD_TO_DEG);
+        assert!((pol[1] * RAD_TO_DEG - 49.348483).abs() * 3600.0 < 0.5, "{}", pol[1] * RAD_TO_DEG);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let x = pol_to_cart(&[1.0, 0.3, 2.5]);
+        let jd1 = epoch(-2500.0);
+        let jd2 = epoch(1850.0);
+        for precess in [precess_ecliptic, precess_equatorial] {
+            let back = precess(&precess(&x, jd1, jd2), jd2, jd1);
+            for i in 0..3 {
+                assert!((back[i] - x[i]).abs() < 1e-13);
+            }
+        }
+    }
+}
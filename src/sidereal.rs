@@ -0,0 +1,90 @@
+//! Ayanamsas for the sidereal zodiac
+//!
+//! The traditional ayanamsas are defined by their value at an epoch and grow
+//! with the general precession in longitude, as in Swiss Ephemeris: the
+//! vernal point of date is precessed to the ecliptic and equinox of the epoch
+//! (see [`crate::precession`]). True Chitra is tied to the mean position of
+//! Spica instead, without aberration. All ayanamsas here are mean, referred
+//! to the mean equinox of date; positions of the true equinox need the
+//! nutation in longitude added.
+
+use crate::constants::*;
+use crate::math::*;
+use crate::{fixstars, precession, Result, SiderealMode};
+
+/// Interval for the rate of change of the ayanamsa (days)
+const SPEED_INTV: f64 = 1.0;
+
+/// Reference epoch (Julian day, TT) and ayanamsa there (degrees)
+fn epoch(mode: SiderealMode) -> Option<(f64, f64)> {
+    match mode {
+        SiderealMode::FaganBradley => Some((2433282.42346, 24.042044444)),
+        SiderealMode::Lahiri => Some((2435553.5, 23.250182778 - 0.004658035)),
+        SiderealMode::Raman => Some((J1900, 360.0 - 338.98556)),
+        SiderealMode::Krishnamurti => Some((J1900, 360.0 - 337.636111)),
+        SiderealMode::User { t0, ayan_t0 } => Some((t0, ayan_t0)),
+        SiderealMode::TrueChitra => None,
+    }
+}
+
+/// Mean ayanamsa at `jd_et` (degrees)
+pub fn ayanamsa(jd_et: f64, mode: SiderealMode) -> Result<f64> {
+    match epoch(mode) {
+        Some((t0, ayan_t0)) => {
+            // Longitude of today's equinox on the ecliptic of the epoch
+            let (lon, _) = precession::precess_lon_lat(0.0, 0.0, jd_et, t0);
+            Ok(ayan_t0 - deg_norm_180(lon))
+        }
+        None => {
+            let (lon, _) = fixstars::mean_position("Spica", jd_et)?;
+            Ok(deg_norm(lon - 180.0))
+        }
+    }
+}
+
+/// Rate of change of the ayanamsa (degrees/day)
+pub fn ayanamsa_speed(jd_et: f64, mode: SiderealMode) -> Result<f64> {
+    let before = ayanamsa(jd_et - SPEED_INTV, mode)?;
+    let after = ayanamsa(jd_et + SPEED_INTV, mode)?;
+    Ok(angle_diff(after, before) / (2.0 * SPEED_INTV))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ayanamsas_2000() {
+        // Swiss Ephemeris at J2000; it precesses Lahiri with IAU 1976 and
+        // Raman and Krishnamurti with Newcomb, which accounts for up to 1"
+        for &(mode, expected) in &[
+            (SiderealMode::FaganBradley, 24.740300),
+            (SiderealMode::Lahiri, 23.857092),
+            (SiderealMode::Raman, 22.410791),
+            (SiderealMode::Krishnamurti, 23.760240),
+        ] {
+            let value = ayanamsa(J2000, mode).unwrap();
+            assert!((value - expected).abs() < 0.001, "{:?}: {}", mode, value);
+        }
+
+        // True Chitra puts the apparent Spica at 180° within its aberration
+        let jd = 2460310.5;
+        let spica = fixstars::fixstar("Spica", jd).unwrap();
+        let (dpsi, _) = crate::nutation::nutation(jd);
+        let sidereal = spica.longitude - ayanamsa(jd, SiderealMode::TrueChitra).unwrap() - dpsi * RAD_TO_DEG;
+        assert!((sidereal - 180.0).abs() < 0.006, "{}", sidereal);
+    }
+
+    #[test]
+    fn test_user_defined() {
+        let mode = SiderealMode::User { t0: J2000, ayan_t0: 0.0 };
+        assert!(ayanamsa(J2000, mode).unwrap().abs() < 1e-12);
+        // General precession over a century, 5029.90"
+        let century = ayanamsa(J2000 + DAYS_PER_CENTURY, mode).unwrap();
+        assert!((century * 3600.0 - 5029.90).abs() < 0.1, "{}", century * 3600.0);
+        assert!(ayanamsa(J2000 - DAYS_PER_CENTURY, mode).unwrap() < 0.0);
+
+        let speed = ayanamsa_speed(J2000, SiderealMode::Lahiri).unwrap();
+        assert!((speed * 365.25 * 3600.0 - 50.29).abs() < 0.01, "{}", speed * 365.25 * 3600.0);
+    }
+}
@@ -0,0 +1,81 @@
+//! Topocentric corrections
+//!
+//! The observer's position on the WGS84 ellipsoid is subtracted from the
+//! geocentric position of a body (diurnal parallax). The observer's velocity
+//! from the Earth's rotation is subtracted from the body's velocity as well,
+//! so topocentric speeds include the diurnal motion.
+
+use crate::constants::*;
+use crate::math::*;
+use crate::{Observer, Position, StateVector};
+
+/// WGS84 equatorial radius (m)
+const WGS84_A: f64 = 6378137.0;
+
+/// WGS84 flattening
+const WGS84_F: f64 = 1.0 / 298.257223563;
+
+/// Earth's rotation rate (sidereal turns per solar day, times 2 pi)
+const EARTH_ROT_RATE: f64 = TWOPI * 1.00273790935;
+
+/// Observer's geocentric equatorial rectangular coordinates (AU)
+///
+/// `lst` is the local sidereal time in degrees.
+pub fn observer_xyz(observer: &Observer, lst: f64) -> [f64; 3] {
+    let lat = observer.latitude * DEG_TO_RAD;
+    let (sin_lat, cos_lat) = lat.sin_cos();
+    let c = 1.0 / (cos_lat * cos_lat + (1.0 - WGS84_F) * (1.0 - WGS84_F) * sin_lat * sin_lat).sqrt();
+    let s = (1.0 - WGS84_F) * (1.0 - WGS84_F) * c;
+
+    let rxy = (WGS84_A * c + observer.altitude) * cos_lat / (AU_KM * 1000.0);
+    let z = (WGS84_A * s + observer.altitude) * sin_lat / (AU_KM * 1000.0);
+    let (sin_lst, cos_lst) = (lst * DEG_TO_RAD).sin_cos();
+    [rxy * cos_lst, rxy * sin_lst, z]
+}
+
+/// Convert a geocentric ecliptic position to topocentric
+///
+/// `lst` is the local sidereal time in degrees and `eps` the obliquity of
+/// the ecliptic in radians, both in the frame of `pos`.
+pub fn topocentric(pos: &Position, observer: &Observer, lst: f64, eps: f64) -> Position {
+    let body = StateVector::from(*pos);
+
+    // Observer position and rotation velocity, rotated onto the ecliptic
+    let obs = observer_xyz(observer, lst);
+    let obs_vel = [-EARTH_ROT_RATE * obs[1], EARTH_ROT_RATE * obs[0], 0.0];
+    let obs = coord_trans(&obs, eps);
+    let obs_vel = coord_trans(&obs_vel, eps);
+
+    let pol = cart_to_pol_speed(&[
+        body.x - obs[0],
+        body.y - obs[1],
+        body.z - obs[2],
+        body.speed_x - obs_vel[0],
+        body.speed_y - obs_vel[1],
+        body.speed_z - obs_vel[2],
+    ]);
+
+    Position {
+        longitude: deg_norm(pol[0] * RAD_TO_DEG),
+        latitude: pol[1] * RAD_TO_DEG,
+        distance: pol[2],
+        speed_longitude: pol[3] * RAD_TO_DEG,
+        speed_latitude: pol[4] * RAD_TO_DEG,
+        speed_distance: pol[5],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observer_radius() {
+        // Equator: equatorial radius; pole: polar radius (6356752 m)
+        let equator = observer_xyz(&Observer { latitude: 0.0, longitude: 0.0, altitude: 0.0 }, 0.0);
+        let pole = observer_xyz(&Observer { latitude: 90.0, longitude: 0.0, altitude: 0.0 }, 0.0);
+        let to_m = AU_KM * 1000.0;
+        assert!((equator[0] * to_m - 6378137.0).abs() < 0.01);
+        assert!((pole[2] * to_m - 6356752.314).abs() < 0.01);
+    }
+}
@@ -5,7 +5,11 @@
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
-use crate::{astrology, calc_houses, calc_ut, constants, julian, Houses, Planet, Position};
+use crate::{
+    astrology, calc, calc_houses, calc_ut, calc_ut_flags, constants, context, delta_t, fixstar_ut, julian,
+    nod_aps_ut, FixedStar, HouseSystem, Houses, KeplerElements, NodAps, NodApsMethod, Planet, Position,
+    Precision, Result, SiderealMode, StateVector,
+};
 
 /// Planet position result for JavaScript
 #[derive(Serialize, Deserialize)]
@@ -15,6 +19,10 @@ pub struct JsPosition {
     pub distance: f64,
     #[serde(rename = "longitudeSpeed")]
     pub longitude_speed: f64,
+    #[serde(rename = "latitudeSpeed")]
+    pub latitude_speed: f64,
+    #[serde(rename = "distanceSpeed")]
+    pub distance_speed: f64,
 }
 
 impl From<Position> for JsPosition {
@@ -24,6 +32,77 @@ impl From<Position> for JsPosition {
             latitude: p.latitude,
             distance: p.distance,
             longitude_speed: p.speed_longitude,
+            latitude_speed: p.speed_latitude,
+            distance_speed: p.speed_distance,
+        }
+    }
+}
+
+/// Lunar nodes and apsides for JavaScript
+#[derive(Serialize, Deserialize)]
+pub struct JsNodAps {
+    pub ascending: JsPosition,
+    pub descending: JsPosition,
+    pub perigee: JsPosition,
+    pub apogee: JsPosition,
+}
+
+impl From<NodAps> for JsNodAps {
+    fn from(n: NodAps) -> Self {
+        JsNodAps {
+            ascending: n.ascending.into(),
+            descending: n.descending.into(),
+            perigee: n.perigee.into(),
+            apogee: n.apogee.into(),
+        }
+    }
+}
+
+/// Fixed star position for JavaScript
+#[derive(Serialize, Deserialize)]
+pub struct JsFixedStar {
+    pub name: String,
+    pub bayer: String,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub magnitude: f64,
+}
+
+impl From<FixedStar> for JsFixedStar {
+    fn from(star: FixedStar) -> Self {
+        JsFixedStar {
+            name: star.name.to_string(),
+            bayer: star.bayer.to_string(),
+            longitude: star.longitude,
+            latitude: star.latitude,
+            magnitude: star.magnitude,
+        }
+    }
+}
+
+/// Rectangular state vector for JavaScript
+#[derive(Serialize, Deserialize)]
+pub struct JsStateVector {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    #[serde(rename = "xSpeed")]
+    pub x_speed: f64,
+    #[serde(rename = "ySpeed")]
+    pub y_speed: f64,
+    #[serde(rename = "zSpeed")]
+    pub z_speed: f64,
+}
+
+impl From<StateVector> for JsStateVector {
+    fn from(sv: StateVector) -> Self {
+        JsStateVector {
+            x: sv.x,
+            y: sv.y,
+            z: sv.z,
+            x_speed: sv.speed_x,
+            y_speed: sv.speed_y,
+            z_speed: sv.speed_z,
         }
     }
 }
@@ -36,16 +115,32 @@ pub struct JsHouses {
     pub mc: f64,
     pub armc: f64,
     pub vertex: f64,
+    #[serde(rename = "equatorialAscendant")]
+    pub equatorial_ascendant: f64,
+    #[serde(rename = "coAscendantKoch")]
+    pub co_ascendant_koch: f64,
+    #[serde(rename = "coAscendantMunkasey")]
+    pub co_ascendant_munkasey: f64,
+    #[serde(rename = "polarAscendant")]
+    pub polar_ascendant: f64,
+    /// Letter of the house system actually used ("O" when Placidus, Koch or
+    /// Gauquelin fall back to Porphyry within the polar circles)
+    pub system: String,
 }
 
 impl From<Houses> for JsHouses {
     fn from(h: Houses) -> Self {
         JsHouses {
-            cusps: h.cusps[1..=12].to_vec(),
+            cusps: if h.sectors.is_empty() { h.cusps[1..=12].to_vec() } else { h.sectors[1..].to_vec() },
             ascendant: h.ascendant,
             mc: h.mc,
             armc: h.armc,
             vertex: h.vertex,
+            equatorial_ascendant: h.equatorial_ascendant,
+            co_ascendant_koch: h.co_ascendant_koch,
+            co_ascendant_munkasey: h.co_ascendant_munkasey,
+            polar_ascendant: h.polar_ascendant,
+            system: h.system.code().to_string(),
         }
     }
 }
@@ -94,48 +189,216 @@ pub fn swe_revjul(jd: f64, gregflag: Option<i32>) -> JsValue {
 ///
 /// # Arguments
 /// * `jd_ut` - Julian Day in Universal Time
-/// * `ipl` - Planet number (0=Sun, 1=Moon, 2=Mercury, ..., 11=True Node)
-/// * `iflag` - Calculation flags (256 = SEFLG_SPEED for speed calculation)
+/// * `ipl` - Planet number (0=Sun, 1=Moon, 2=Mercury, ..., 11=True Node,
+///   12=Mean Apogee, 13=Osculating Apogee, 15=Chiron, 16=Pholus, 17=Ceres,
+///   18=Pallas, 19=Juno, 20=Vesta)
+/// * `iflag` - Calculation flags (256 = SEFLG_SPEED, 16 = SEFLG_TRUEPOS,
+///   1024 = SEFLG_NOABERR, 64 = SEFLG_NONUT, 32 = SEFLG_J2000,
+///   2048 = SEFLG_EQUATORIAL, 32768 = SEFLG_TOPOCTR after swe_set_topo,
+///   65536 = SEFLG_SIDEREAL in the mode of swe_set_sid_mode)
 ///
 /// # Returns
-/// Position object with longitude, latitude, distance, longitudeSpeed
+/// Position object with longitude, latitude, distance, longitudeSpeed, latitudeSpeed
+/// and distanceSpeed.
+/// With SEFLG_EQUATORIAL, longitude and latitude hold right ascension and declination.
+/// With SEFLG_XYZ (4096), an object with x, y, z, xSpeed, ySpeed, zSpeed instead.
 #[wasm_bindgen(js_name = swe_calc_ut)]
 pub fn swe_calc_ut(jd_ut: f64, ipl: i32, iflag: Option<i32>) -> JsValue {
+    calc_to_js(ipl, iflag.unwrap_or(0), |planet, flags| calc_ut_flags(jd_ut, planet, flags))
+}
+
+/// Calculate planet position for Terrestrial Time (ET)
+///
+/// Same as swe_calc_ut, with `jd_et` in TT instead of UT.
+#[wasm_bindgen(js_name = swe_calc)]
+pub fn swe_calc(jd_et: f64, ipl: i32, iflag: Option<i32>) -> JsValue {
+    calc_to_js(ipl, iflag.unwrap_or(0), |planet, flags| calc(jd_et, planet, flags))
+}
+
+/// Position or state vector, as selected by SEFLG_XYZ
+#[derive(Serialize)]
+#[serde(untagged)]
+enum JsCalcResult {
+    Position(JsPosition),
+    StateVector(JsStateVector),
+}
+
+/// Run a position calculation, honoring SEFLG_XYZ
+fn calc_result<F>(planet: Planet, flags: i32, calc_fn: F) -> Result<JsCalcResult>
+where
+    F: Fn(Planet, i32) -> Result<Position>,
+{
+    if flags & constants::SEFLG_XYZ != 0 {
+        let xyz_flags = (flags | constants::SEFLG_SPEED) & !constants::SEFLG_XYZ;
+        return calc_fn(planet, xyz_flags).map(|pos| JsCalcResult::StateVector(StateVector::from(pos).into()));
+    }
+    calc_fn(planet, flags).map(|pos| JsCalcResult::Position(pos.into()))
+}
+
+/// Run a position calculation and convert the result, honoring SEFLG_XYZ
+fn calc_to_js<F>(ipl: i32, flags: i32, calc_fn: F) -> JsValue
+where
+    F: Fn(Planet, i32) -> Result<Position>,
+{
     let planet = match Planet::from_i32(ipl) {
         Some(p) => p,
         None => return JsValue::NULL,
     };
 
-    let flags = iflag.unwrap_or(0);
-    let calc_speed = (flags & constants::SEFLG_SPEED) != 0;
+    match calc_result(planet, flags, calc_fn) {
+        Ok(result) => serde_wasm_bindgen::to_value(&result).unwrap_or(JsValue::NULL),
+        Err(_) => JsValue::NULL,
+    }
+}
 
-    match calc_ut(jd_ut, planet, calc_speed) {
-        Ok(pos) => {
-            let js_pos: JsPosition = pos.into();
-            serde_wasm_bindgen::to_value(&js_pos).unwrap_or(JsValue::NULL)
-        }
+/// Calculate the lunar nodes and apsides
+///
+/// # Arguments
+/// * `jd_ut` - Julian Day in Universal Time
+/// * `ipl` - Planet number, only 1 (Moon) is supported
+/// * `iflag` - Calculation flags as for swe_calc_ut (SEFLG_TOPOCTR is ignored)
+/// * `method` - 1 = SE_NODBIT_MEAN (default), 2 = SE_NODBIT_OSCU
+///
+/// # Returns
+/// Object with ascending, descending, perigee and apogee positions, or null
+#[wasm_bindgen(js_name = swe_nod_aps_ut)]
+pub fn swe_nod_aps_ut(jd_ut: f64, ipl: i32, iflag: Option<i32>, method: Option<i32>) -> JsValue {
+    let planet = match Planet::from_i32(ipl) {
+        Some(p) => p,
+        None => return JsValue::NULL,
+    };
+    let method = match method.unwrap_or(constants::SE_NODBIT_MEAN) {
+        m if m & constants::SE_NODBIT_OSCU != 0 => NodApsMethod::Osculating,
+        _ => NodApsMethod::Mean,
+    };
+
+    match nod_aps_ut(jd_ut, planet, iflag.unwrap_or(0), method) {
+        Ok(nod_aps) => serde_wasm_bindgen::to_value(&JsNodAps::from(nod_aps)).unwrap_or(JsValue::NULL),
         Err(_) => JsValue::NULL,
     }
 }
 
-/// Calculate house cusps (Placidus system)
+/// Convert a UTC date to Julian days, handling leap seconds
+///
+/// # Arguments
+/// * `year`, `month`, `day`, `hour`, `min` - UTC date and time
+/// * `sec` - Seconds, up to 60.999 during a leap second
+/// * `gregflag` - Calendar flag: 1 for Gregorian (default), 0 for Julian
+///
+/// # Returns
+/// Array [jdEt, jdUt] (TT and UT1), or null for an invalid date
+#[wasm_bindgen(js_name = swe_utc_to_jd)]
+pub fn swe_utc_to_jd(
+    year: i32,
+    month: i32,
+    day: i32,
+    hour: i32,
+    min: i32,
+    sec: f64,
+    gregflag: Option<i32>,
+) -> JsValue {
+    let flag = gregflag.unwrap_or(constants::SE_GREG_CAL);
+    match julian::utc_to_jd(year, month, day, hour, min, sec, flag) {
+        Ok((jd_et, jd_ut)) => serde_wasm_bindgen::to_value(&[jd_et, jd_ut]).unwrap_or(JsValue::NULL),
+        Err(_) => JsValue::NULL,
+    }
+}
+
+/// Calculate house cusps
 ///
 /// # Arguments
 /// * `jd_ut` - Julian Day in Universal Time
 /// * `lat` - Geographic latitude in degrees
 /// * `lon` - Geographic longitude in degrees
-/// * `hsys` - House system (only 'P' for Placidus supported, ignored)
+/// * `hsys` - House system letter: 'P' Placidus (default), 'K' Koch,
+///   'E' or 'A' Equal, 'D' Equal from MC, 'W' Whole Sign, 'O' Porphyry,
+///   'R' Regiomontanus, 'C' Campanus, 'B' Alcabitius, 'M' Morinus,
+///   'T' Topocentric, 'X' Meridian, 'G' Gauquelin sectors
 ///
 /// # Returns
-/// Object with cusps array (12 elements), ascendant, mc, armc, vertex
+/// Object with cusps array (12 elements, 36 for 'G'), ascendant, mc, armc, vertex,
+/// equatorialAscendant, coAscendantKoch, coAscendantMunkasey, polarAscendant
+/// and the letter of the system actually used, or null for an unknown letter
 #[wasm_bindgen(js_name = swe_houses)]
-pub fn swe_houses(jd_ut: f64, lat: f64, lon: f64, _hsys: Option<String>) -> JsValue {
-    // Note: We only support Placidus, hsys parameter is ignored
-    match calc_houses(jd_ut, lat, lon) {
-        Ok(houses) => {
-            let js_houses: JsHouses = houses.into();
-            serde_wasm_bindgen::to_value(&js_houses).unwrap_or(JsValue::NULL)
-        }
+pub fn swe_houses(jd_ut: f64, lat: f64, lon: f64, hsys: Option<String>) -> JsValue {
+    match house_system(hsys) {
+        Some(hsys) => houses_to_js(calc_houses(jd_ut, lat, lon, hsys)),
+        None => JsValue::NULL,
+    }
+}
+
+/// Calculate house cusps for a given ARMC and obliquity
+///
+/// # Arguments
+/// * `armc` - Right ascension of the MC in degrees
+/// * `lat` - Geographic latitude in degrees
+/// * `eps` - Obliquity of the ecliptic in degrees
+/// * `hsys` - House system letter as for swe_houses
+///
+/// # Returns
+/// Same object as swe_houses, or null
+#[wasm_bindgen(js_name = swe_houses_armc)]
+pub fn swe_houses_armc(armc: f64, lat: f64, eps: f64, hsys: Option<String>) -> JsValue {
+    match house_system(hsys) {
+        Some(hsys) => houses_to_js(crate::houses_armc(armc, lat, eps, hsys)),
+        None => JsValue::NULL,
+    }
+}
+
+/// House position of a body
+///
+/// # Arguments
+/// * `armc` - Right ascension of the MC in degrees
+/// * `lat` - Geographic latitude in degrees
+/// * `eps` - Obliquity of the ecliptic in degrees
+/// * `hsys` - House system letter as for swe_houses
+/// * `lon` - Ecliptic longitude of the body in degrees
+/// * `lat_body` - Ecliptic latitude of the body in degrees
+///
+/// # Returns
+/// House position in [1, 13), Gauquelin sector in [1, 37) for 'G', or NaN on error
+#[wasm_bindgen(js_name = swe_house_pos)]
+pub fn swe_house_pos(armc: f64, lat: f64, eps: f64, hsys: Option<String>, lon: f64, lat_body: f64) -> f64 {
+    house_system(hsys)
+        .and_then(|hsys| crate::house_pos(armc, lat, eps, hsys, lon, lat_body).ok())
+        .unwrap_or(f64::NAN)
+}
+
+/// Gauquelin sector of a body
+///
+/// # Arguments
+/// * `jd_ut` - Julian Day in Universal Time
+/// * `ipl` - Planet ID
+/// * `lat` - Geographic latitude in degrees
+/// * `lon` - Geographic longitude in degrees
+/// * `imeth` - 0 = with the latitude of the body (default), 1 = without;
+///   the rise and set methods 2-5 are not supported
+///
+/// # Returns
+/// Sector in [1, 37), or NaN on error
+#[wasm_bindgen(js_name = swe_gauquelin_sector)]
+pub fn swe_gauquelin_sector(jd_ut: f64, ipl: i32, lat: f64, lon: f64, imeth: Option<i32>) -> f64 {
+    let with_latitude = match imeth.unwrap_or(0) {
+        0 => true,
+        1 => false,
+        _ => return f64::NAN,
+    };
+    Planet::from_i32(ipl)
+        .and_then(|planet| crate::gauquelin_sector(jd_ut, planet, lat, lon, with_latitude).ok())
+        .unwrap_or(f64::NAN)
+}
+
+/// House system for a Swiss letter: Placidus when missing, `None` when unknown
+fn house_system(hsys: Option<String>) -> Option<HouseSystem> {
+    match hsys.and_then(|s| s.chars().next()) {
+        Some(code) => HouseSystem::from_code(code),
+        None => Some(HouseSystem::default()),
+    }
+}
+
+fn houses_to_js(houses: Result<Houses>) -> JsValue {
+    match houses {
+        Ok(houses) => serde_wasm_bindgen::to_value(&JsHouses::from(houses)).unwrap_or(JsValue::NULL),
         Err(_) => JsValue::NULL,
     }
 }
@@ -156,8 +419,19 @@ pub fn swe_get_planet_name(ipl: i32) -> String {
         9 => "Pluto".to_string(),
         10 => "Mean Node".to_string(),
         11 => "True Node".to_string(),
+        12 => "Mean Apogee".to_string(),
+        13 => "Osc. Apogee".to_string(),
         14 => "Earth".to_string(),
-        _ => format!("Planet {}", ipl),
+        15 => "Chiron".to_string(),
+        16 => "Pholus".to_string(),
+        17 => "Ceres".to_string(),
+        18 => "Pallas".to_string(),
+        19 => "Juno".to_string(),
+        20 => "Vesta".to_string(),
+        _ => match Planet::from_i32(ipl) {
+            Some(Planet::Custom(index)) => context::get_orbit_name(index).unwrap_or_default(),
+            _ => format!("Planet {}", ipl),
+        },
     }
 }
 
@@ -230,11 +504,56 @@ impl SE {
         11
     }
 
+    #[wasm_bindgen(getter)]
+    pub fn MEAN_APOG() -> i32 {
+        12
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn OSCU_APOG() -> i32 {
+        13
+    }
+
     #[wasm_bindgen(getter)]
     pub fn EARTH() -> i32 {
         14
     }
 
+    #[wasm_bindgen(getter)]
+    pub fn CHIRON() -> i32 {
+        15
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn PHOLUS() -> i32 {
+        16
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn CERES() -> i32 {
+        17
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn PALLAS() -> i32 {
+        18
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn JUNO() -> i32 {
+        19
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn VESTA() -> i32 {
+        20
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn FICT_OFFSET() -> i32 {
+        constants::SE_FICT_OFFSET
+    }
+
     #[wasm_bindgen(getter)]
     pub fn GREG_CAL() -> i32 {
         1
@@ -245,10 +564,90 @@ impl SE {
         0
     }
 
+    #[wasm_bindgen(getter)]
+    pub fn FLG_TRUEPOS() -> i32 {
+        16
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn FLG_NONUT() -> i32 {
+        64
+    }
+
     #[wasm_bindgen(getter)]
     pub fn FLG_SPEED() -> i32 {
         256
     }
+
+    #[wasm_bindgen(getter)]
+    pub fn FLG_NOABERR() -> i32 {
+        1024
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn FLG_EQUATORIAL() -> i32 {
+        2048
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn FLG_XYZ() -> i32 {
+        4096
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn FLG_TOPOCTR() -> i32 {
+        32768
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn FLG_SIDEREAL() -> i32 {
+        constants::SEFLG_SIDEREAL
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn DELTAT_AUTOMATIC() -> f64 {
+        constants::SE_DELTAT_AUTOMATIC
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn NODBIT_MEAN() -> i32 {
+        1
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn NODBIT_OSCU() -> i32 {
+        2
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn SIDM_FAGAN_BRADLEY() -> i32 {
+        constants::SE_SIDM_FAGAN_BRADLEY
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn SIDM_LAHIRI() -> i32 {
+        constants::SE_SIDM_LAHIRI
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn SIDM_RAMAN() -> i32 {
+        constants::SE_SIDM_RAMAN
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn SIDM_KRISHNAMURTI() -> i32 {
+        constants::SE_SIDM_KRISHNAMURTI
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn SIDM_TRUE_CITRA() -> i32 {
+        constants::SE_SIDM_TRUE_CITRA
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn SIDM_USER() -> i32 {
+        constants::SE_SIDM_USER
+    }
 }
 
 /// Batch calculation for multiple planets
@@ -256,23 +655,24 @@ impl SE {
 /// # Arguments
 /// * `jd_ut` - Julian Day in Universal Time
 /// * `planets` - Array of planet numbers
-/// * `iflag` - Calculation flags
+/// * `iflag` - Calculation flags as for swe_calc_ut
 ///
 /// # Returns
-/// Object mapping planet numbers to position objects
+/// Object mapping planet numbers to position objects, or to state vectors with
+/// SEFLG_XYZ (4096). Bodies that cannot be calculated, such as the minor bodies
+/// outside 1900-2100, are left out.
 #[wasm_bindgen(js_name = swe_calc_ut_batch)]
 pub fn swe_calc_ut_batch(jd_ut: f64, planets: &[i32], iflag: Option<i32>) -> JsValue {
     use std::collections::HashMap;
 
     let flags = iflag.unwrap_or(0);
-    let calc_speed = (flags & constants::SEFLG_SPEED) != 0;
 
-    let mut results: HashMap<i32, JsPosition> = HashMap::new();
+    let mut results: HashMap<i32, JsCalcResult> = HashMap::new();
 
     for &ipl in planets {
         if let Some(planet) = Planet::from_i32(ipl) {
-            if let Ok(pos) = calc_ut(jd_ut, planet, calc_speed) {
-                results.insert(ipl, pos.into());
+            if let Ok(result) = calc_result(planet, flags, |planet, flags| calc_ut_flags(jd_ut, planet, flags)) {
+                results.insert(ipl, result);
             }
         }
     }
@@ -284,16 +684,164 @@ pub fn swe_calc_ut_batch(jd_ut: f64, planets: &[i32], iflag: Option<i32>) -> JsV
 ///
 /// # Arguments
 /// * `jd_ut` - Julian Day in Universal Time
-/// * `iflag` - Calculation flags
+/// * `iflag` - Calculation flags as for swe_calc_ut
 ///
 /// # Returns
-/// Object with all planet positions
+/// Object with all planet positions, or state vectors with SEFLG_XYZ
 #[wasm_bindgen(js_name = swe_calc_ut_all)]
 pub fn swe_calc_ut_all(jd_ut: f64, iflag: Option<i32>) -> JsValue {
     let all_planets = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 11]; // All planets + True Node
     swe_calc_ut_batch(jd_ut, &all_planets, iflag)
 }
 
+/// Set the observer for topocentric positions (SEFLG_TOPOCTR = 32768)
+///
+/// # Arguments
+/// * `geolon` - Geographic longitude in degrees (east positive)
+/// * `geolat` - Geographic latitude in degrees (north positive)
+/// * `altitude` - Altitude above sea level in meters
+#[wasm_bindgen(js_name = swe_set_topo)]
+pub fn swe_set_topo(geolon: f64, geolat: f64, altitude: f64) {
+    context::set_topo(geolon, geolat, altitude);
+}
+
+/// Set the series precision for later calculations
+///
+/// # Arguments
+/// * `level` - 0 = Fast (within 5'), 1 = Standard (within 10"), 2 = High (all terms)
+///
+/// Unknown levels select Standard.
+#[wasm_bindgen(js_name = swe_set_precision)]
+pub fn swe_set_precision(level: i32) {
+    let precision = match level {
+        0 => Precision::Fast,
+        2 => Precision::High,
+        _ => Precision::Standard,
+    };
+    context::set_precision(precision);
+}
+
+/// Get the series precision in effect (0 = Fast, 1 = Standard, 2 = High)
+#[wasm_bindgen(js_name = swe_get_precision)]
+pub fn swe_get_precision() -> i32 {
+    match context::get_precision() {
+        Precision::Fast => 0,
+        Precision::Standard => 1,
+        Precision::High => 2,
+    }
+}
+
+/// Get delta-T (TT - UT) in days
+#[wasm_bindgen(js_name = swe_deltat)]
+pub fn swe_deltat(jd_ut: f64) -> f64 {
+    delta_t(jd_ut)
+}
+
+/// Select the sidereal zodiac (ayanamsa)
+///
+/// Positions with SEFLG_SIDEREAL use this mode, as in Swiss Ephemeris. The
+/// chart functions (getNatalChart, calculateChart) and swe_houses become
+/// sidereal as well, until a negative `sid_mode` returns to the tropical zodiac.
+///
+/// # Arguments
+/// * `sid_mode` - 0 = Fagan/Bradley, 1 = Lahiri, 3 = Raman, 5 = Krishnamurti,
+///   27 = True Chitra, 255 = user-defined
+/// * `t0` - Reference date (Julian day, TT) for user-defined
+/// * `ayan_t0` - Ayanamsa at `t0` in degrees for user-defined
+///
+/// # Returns
+/// false for a mode that is not supported, which leaves the current mode unchanged
+#[wasm_bindgen(js_name = swe_set_sid_mode)]
+pub fn swe_set_sid_mode(sid_mode: i32, t0: Option<f64>, ayan_t0: Option<f64>) -> bool {
+    if sid_mode < 0 {
+        context::set_sid_mode(None);
+        return true;
+    }
+    match SiderealMode::from_i32(sid_mode, t0.unwrap_or(0.0), ayan_t0.unwrap_or(0.0)) {
+        Some(mode) => {
+            context::set_sid_mode(Some(mode));
+            true
+        }
+        None => false,
+    }
+}
+
+/// Get the ayanamsa of the current sidereal mode in degrees
+///
+/// # Arguments
+/// * `jd_ut` - Julian Day in Universal Time
+///
+/// # Returns
+/// Mean ayanamsa (Fagan/Bradley if no mode is set), or NaN on error
+#[wasm_bindgen(js_name = swe_get_ayanamsa_ut)]
+pub fn swe_get_ayanamsa_ut(jd_ut: f64) -> f64 {
+    crate::get_ayanamsa_ut(jd_ut).unwrap_or(f64::NAN)
+}
+
+/// Fix delta-T for later calculations
+///
+/// # Arguments
+/// * `t_acc` - Delta-T in days, or SE_DELTAT_AUTOMATIC (-1e-10) for the built-in model
+#[wasm_bindgen(js_name = swe_set_delta_t_userdef)]
+pub fn swe_set_delta_t_userdef(t_acc: f64) {
+    let userdef = if t_acc == constants::SE_DELTAT_AUTOMATIC { None } else { Some(t_acc) };
+    context::set_delta_t_userdef(userdef);
+}
+
+/// Register a body from its Keplerian elements
+///
+/// Elements are heliocentric, referred to the J2000 ecliptic and equinox, as in
+/// an MPC export. The longitude of perihelion is the node plus the argument of
+/// perihelion. Only elliptic orbits (e < 1) are supported.
+///
+/// # Arguments
+/// * `name` - Name returned by swe_get_planet_name
+/// * `epoch` - Epoch of the elements, Julian Day (TT)
+/// * `a` - Semi-major axis in AU
+/// * `e` - Eccentricity
+/// * `i`, `node`, `peri`, `m` - Inclination, ascending node, longitude of
+///   perihelion and mean anomaly at the epoch, in degrees
+///
+/// # Returns
+/// Planet number for swe_calc_ut and the other calculations (40 and up), or -1
+#[wasm_bindgen(js_name = swe_register_orbit)]
+#[allow(clippy::too_many_arguments)]
+pub fn swe_register_orbit(name: &str, epoch: f64, a: f64, e: f64, i: f64, node: f64, peri: f64, m: f64) -> i32 {
+    let elements = KeplerElements {
+        epoch,
+        semi_major: a,
+        eccentricity: e,
+        inclination: i,
+        asc_node: node,
+        lon_perihelion: peri,
+        mean_anomaly: m,
+    };
+    context::register_orbit(name, elements).map(Planet::id).unwrap_or(-1)
+}
+
+/// Forget all bodies registered with swe_register_orbit
+#[wasm_bindgen(js_name = swe_clear_orbits)]
+pub fn swe_clear_orbits() {
+    context::clear_orbits();
+}
+
+/// Calculate the apparent position of a fixed star
+///
+/// # Arguments
+/// * `star` - Traditional name ("Regulus") or Bayer designation ("alLeo")
+/// * `jd_ut` - Julian Day (UT)
+///
+/// # Returns
+/// Object with name, bayer, longitude, latitude and magnitude, or null for an
+/// unknown star
+#[wasm_bindgen(js_name = swe_fixstar_ut)]
+pub fn swe_fixstar_ut(star: &str, jd_ut: f64) -> JsValue {
+    match fixstar_ut(star, jd_ut) {
+        Ok(pos) => serde_wasm_bindgen::to_value(&JsFixedStar::from(pos)).unwrap_or(JsValue::NULL),
+        Err(_) => JsValue::NULL,
+    }
+}
+
 // ============================================================================
 // High-Level Astrological Functions
 // These return ready-to-use data structures
@@ -331,12 +879,26 @@ pub struct JsNatalChart {
     pub houses: Vec<JsHouseCusp>,
     pub ascendant: f64,
     pub midheaven: f64,
+    pub house_system: String,
+    /// Ayanamsa of a sidereal chart (see swe_set_sid_mode), null when tropical
+    pub ayanamsa: Option<f64>,
     pub north_node: JsNorthNode,
+    pub lilith: JsLilith,
 }
 
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct JsNorthNode {
+    pub node_key: String,
+    pub longitude: f64,
+    pub sign_key: String,
+    pub sign_degree: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsLilith {
+    pub lilith_key: String,
     pub longitude: f64,
     pub sign_key: String,
     pub sign_degree: f64,
@@ -417,6 +979,68 @@ pub struct JsHeliocentricChart {
     pub planets: Vec<JsPlanetPosition>,
 }
 
+/// Sign of a position in one divisional chart
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsVarga {
+    /// 1 for the Rasi chart (D1), 9 for the Navamsa (D9), ...
+    pub division: u8,
+    pub varga_key: String,
+    pub sign_key: String,
+}
+
+/// Graha or Lagna of a Vedic chart for JavaScript
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsVedicPosition {
+    pub planet_key: String,
+    pub longitude: f64,
+    pub sign_key: String,
+    pub sign_degree: f64,
+    pub nakshatra_key: String,
+    pub nakshatra_pada: u8,
+    pub nakshatra_lord: String,
+    pub house: u8,
+    pub is_retrograde: bool,
+    pub vargas: Vec<JsVarga>,
+}
+
+/// Vedic chart for JavaScript
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsVedicChart {
+    /// Swiss Ephemeris SE_SIDM_* number of the ayanamsa
+    pub sid_mode: i32,
+    pub ayanamsa: f64,
+    pub ascendant: JsVedicPosition,
+    pub planets: Vec<JsVedicPosition>,
+}
+
+impl From<&astrology::VedicPosition> for JsVedicPosition {
+    fn from(p: &astrology::VedicPosition) -> Self {
+        JsVedicPosition {
+            planet_key: p.planet_key.to_string(),
+            longitude: p.longitude,
+            sign_key: p.sign_key.to_string(),
+            sign_degree: p.sign_degree,
+            nakshatra_key: p.nakshatra_key.to_string(),
+            nakshatra_pada: p.nakshatra_pada,
+            nakshatra_lord: p.nakshatra_lord.to_string(),
+            house: p.house,
+            is_retrograde: p.is_retrograde,
+            vargas: p
+                .vargas
+                .iter()
+                .map(|(varga, sign)| JsVarga {
+                    division: varga.division(),
+                    varga_key: varga.as_str().to_string(),
+                    sign_key: sign.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
 /// Get all planetary positions at a given time
 ///
 /// Returns array of planet positions with sign, degree, and retrograde status
@@ -449,12 +1073,28 @@ pub fn get_all_planetary_positions(jd_ut: f64) -> JsValue {
 /// * `jd_ut` - Julian Day in Universal Time
 /// * `lat` - Geographic latitude
 /// * `lon` - Geographic longitude
+/// * `node` - "true" (default) or "mean" lunar node
+/// * `lilith` - "mean" (default) or "oscu" (also "true") Black Moon Lilith (lunar apogee)
 ///
 /// # Returns
-/// Complete natal chart with planets, houses, angles, and north node
+/// Complete natal chart with planets, houses, angles, north node and Lilith
 #[wasm_bindgen(js_name = getNatalChart)]
-pub fn get_natal_chart(jd_ut: f64, lat: f64, lon: f64) -> JsValue {
-    match astrology::get_natal_chart(jd_ut, lat, lon) {
+pub fn get_natal_chart(
+    jd_ut: f64,
+    lat: f64,
+    lon: f64,
+    node: Option<String>,
+    lilith: Option<String>,
+) -> JsValue {
+    let node_type = match node.as_deref() {
+        Some("mean") => astrology::NodeType::Mean,
+        _ => astrology::NodeType::True,
+    };
+    let lilith_type = match lilith.as_deref() {
+        Some("oscu" | "true") => astrology::LilithType::Osculating,
+        _ => astrology::LilithType::Mean,
+    };
+    match astrology::get_natal_chart_with_points(jd_ut, lat, lon, node_type, lilith_type) {
         Ok(chart) => {
             let js_chart = JsNatalChart {
                 planets: chart
@@ -483,11 +1123,20 @@ pub fn get_natal_chart(jd_ut: f64, lat: f64, lon: f64) -> JsValue {
                     .collect(),
                 ascendant: chart.ascendant,
                 midheaven: chart.midheaven,
+                house_system: chart.house_system.code().to_string(),
+                ayanamsa: chart.ayanamsa,
                 north_node: JsNorthNode {
+                    node_key: chart.node_type.as_str().to_string(),
                     longitude: chart.north_node_longitude,
                     sign_key: chart.north_node_sign.to_string(),
                     sign_degree: chart.north_node_degree,
                 },
+                lilith: JsLilith {
+                    lilith_key: chart.lilith_type.as_str().to_string(),
+                    longitude: chart.lilith_longitude,
+                    sign_key: chart.lilith_sign.to_string(),
+                    sign_degree: chart.lilith_degree,
+                },
             };
             serde_wasm_bindgen::to_value(&js_chart).unwrap_or(JsValue::NULL)
         }
@@ -560,6 +1209,36 @@ pub fn get_heliocentric_chart(jd_ut: f64) -> JsValue {
     }
 }
 
+/// Get Vedic chart
+///
+/// Sidereal positions of the nine grahas (Rahu is the mean node, Ketu the
+/// point opposite) and the Lagna, with nakshatra, pada, whole sign house and
+/// the sign in each divisional chart D1-D60. The ayanamsa is the one selected
+/// with swe_set_sid_mode, or Lahiri when the zodiac is tropical.
+///
+/// # Arguments
+/// * `jd_ut` - Julian Day in Universal Time
+/// * `lat` - Geographic latitude
+/// * `lon` - Geographic longitude
+///
+/// # Returns
+/// Vedic chart with ascendant and planets, or null on error
+#[wasm_bindgen(js_name = getVedicChart)]
+pub fn get_vedic_chart(jd_ut: f64, lat: f64, lon: f64) -> JsValue {
+    match astrology::get_vedic_chart(jd_ut, lat, lon) {
+        Ok(chart) => {
+            let js_chart = JsVedicChart {
+                sid_mode: chart.sid_mode.id(),
+                ayanamsa: chart.ayanamsa,
+                ascendant: JsVedicPosition::from(&chart.ascendant),
+                planets: chart.planets.iter().map(JsVedicPosition::from).collect(),
+            };
+            serde_wasm_bindgen::to_value(&js_chart).unwrap_or(JsValue::NULL)
+        }
+        Err(_) => JsValue::NULL,
+    }
+}
+
 /// Calculate moon phase
 ///
 /// # Arguments
@@ -968,7 +1647,7 @@ pub fn calculate_chart(year: i32, month: i32, day: i32, hour: f64, lat: f64, lon
         Ok(p) => p,
         Err(_) => return JsValue::NULL,
     };
-    let houses = match calc_houses(jd, lat, lon) {
+    let houses = match calc_houses(jd, lat, lon, HouseSystem::Placidus) {
         Ok(h) => h,
         Err(_) => return JsValue::NULL,
     };
@@ -1019,4 +1698,48 @@ mod tests {
         let jd = swe_julday(2000, 1, 1, 12.0, Some(1));
         assert!((jd - 2451545.0).abs() < 0.0001);
     }
+
+    #[test]
+    fn test_house_system() {
+        assert_eq!(house_system(None), Some(HouseSystem::Placidus));
+        assert_eq!(house_system(Some("r".to_string())), Some(HouseSystem::Regiomontanus));
+        assert_eq!(house_system(Some("Z".to_string())), None);
+        assert!(swe_house_pos(123.4, 47.38, 23.44, Some("Z".to_string()), 100.0, 0.0).is_nan());
+    }
+
+    #[test]
+    fn test_gauquelin_sector_high_latitude() {
+        // The Moon at Trondheim, circumpolar on some of these days
+        for day in 0..365 {
+            let jd = 2460676.5 + day as f64;
+            assert!(!swe_gauquelin_sector(jd, 1, 63.43, 10.40, None).is_nan(), "day {}", day);
+        }
+    }
+
+    #[test]
+    fn test_calc_result_xyz() {
+        // The batch calls go through calc_result, which gives state vectors with SEFLG_XYZ
+        let jd = swe_julday(2024, 3, 1, 6.0, Some(1));
+        let calc_fn = |planet, flags| calc_ut_flags(jd, planet, flags);
+        let expected = crate::calc_ut_xyz(jd, Planet::Mars, 0).unwrap();
+        match calc_result(Planet::Mars, constants::SEFLG_XYZ, calc_fn).unwrap() {
+            JsCalcResult::StateVector(sv) => {
+                assert_eq!((sv.x, sv.y, sv.z), (expected.x, expected.y, expected.z));
+                assert_eq!(sv.x_speed, expected.speed_x);
+            }
+            JsCalcResult::Position(_) => panic!("SEFLG_XYZ gave polar coordinates"),
+        }
+        assert!(matches!(calc_result(Planet::Mars, 0, calc_fn).unwrap(), JsCalcResult::Position(_)));
+    }
+
+    #[test]
+    fn test_set_sid_mode() {
+        assert!(swe_set_sid_mode(1, None, None));
+        assert_eq!(context::get_sid_mode(), Some(SiderealMode::Lahiri));
+        // Yukteshwar (7) is not supported: the mode stays Lahiri
+        assert!(!swe_set_sid_mode(7, None, None));
+        assert_eq!(context::get_sid_mode(), Some(SiderealMode::Lahiri));
+        assert!(swe_set_sid_mode(-1, None, None));
+        assert_eq!(context::get_sid_mode(), None);
+    }
 }
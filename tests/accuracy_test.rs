@@ -2,7 +2,7 @@
 //!
 //! Reference values from swetest with Moshier ephemeris for 2000-01-01 12:00 UT
 
-use tailored_ephemeris::{calc_ut, calc_houses, julian, Planet};
+use tailored_ephemeris::{calc_ut, calc_houses, julian, set_precision, HouseSystem, Planet, Precision};
 
 /// Reference values from Swiss Ephemeris (swetest -b1.1.2000 -ut12:00 -eswe)
 /// Format: (longitude, speed, distance)
@@ -150,6 +150,82 @@ fn test_accuracy_2035() {
     assert!(max_err < 45.0, "Error exceeds 0.75 degrees for 2035");
 }
 
+/// Error shared by every precision tier: theory error of the full series
+/// and the remaining delta-T differences
+const THEORY_ALLOWANCE_ARCSEC: f64 = 20.0;
+
+#[test]
+fn test_accuracy_precision_tiers() {
+    let references = [
+        (julian::julday(2000, 1, 1, 12.0, 1), &SE_REFERENCE_J2000[..]),
+        (julian::julday(1925, 1, 1, 12.0, 1), &SE_REFERENCE_1925[..]),
+        (julian::julday(2035, 1, 1, 12.0, 1), &SE_REFERENCE_2035[..]),
+    ];
+
+    println!("\n=== Precision Tier Test ===\n");
+
+    for precision in [Precision::Fast, Precision::Standard, Precision::High] {
+        set_precision(precision);
+        let bound = THEORY_ALLOWANCE_ARCSEC + precision.max_error_arcsec();
+        let mut max_error_arcsec = 0.0f64;
+
+        for (jd, reference) in references.iter() {
+            // The node magnifies lunar series errors; multi_era_test bounds it
+            for (planet, se_lon, _se_speed) in reference.iter().filter(|r| r.0 != Planet::TrueNode) {
+                let pos = calc_ut(*jd, *planet, false).expect("calc failed");
+                let diff = (pos.longitude - se_lon).abs();
+                let diff_arcsec = if diff > 180.0 { 360.0 - diff } else { diff } * 3600.0;
+
+                assert!(diff_arcsec < bound,
+                        "{:?} at {:?} off by {:.1}\" (bound {:.0}\")", planet, precision, diff_arcsec, bound);
+                max_error_arcsec = max_error_arcsec.max(diff_arcsec);
+            }
+        }
+
+        println!("{:<10} max error {:>6.1}\" (bound {:.0}\")",
+                 format!("{:?}", precision), max_error_arcsec, bound);
+    }
+
+    set_precision(Precision::default());
+}
+
+#[test]
+fn test_speed_accuracy() {
+    let references = [
+        (julian::julday(2000, 1, 1, 12.0, 1), &SE_REFERENCE_J2000[..]),
+        (julian::julday(1925, 1, 1, 12.0, 1), &SE_REFERENCE_1925[..]),
+        (julian::julday(2035, 1, 1, 12.0, 1), &SE_REFERENCE_2035[..]),
+    ];
+
+    println!("\n=== Speed Accuracy Test ===\n");
+
+    for (jd, reference) in references.iter() {
+        for (planet, _se_lon, se_speed) in reference.iter() {
+            let pos = calc_ut(*jd, *planet, true).expect("calc failed");
+            let diff = pos.speed_longitude - se_speed;
+            // The Moon's speed comes from the truncated ELP2000 series. The node
+            // speed follows the Sun's perturbation (checked against an integrated
+            // orbit in nodes.rs); the reference differences the node of its own
+            // truncated lunar series, which is only good to about 8e-3
+            let bound = match planet {
+                Planet::Moon => 4e-4,
+                Planet::TrueNode => 8e-3,
+                _ => 6e-5,
+            };
+
+            println!("{:<10} {:>12.7} {:>12.7} {:>10.2e}", format!("{:?}", planet), pos.speed_longitude, se_speed, diff);
+            assert!(diff.abs() < bound,
+                    "{:?} speed off by {:.2e} deg/day at JD {}", planet, diff, jd);
+
+            // Latitude and distance speeds match a difference of the positions
+            let before = calc_ut(jd - 0.05, *planet, false).unwrap();
+            let after = calc_ut(jd + 0.05, *planet, false).unwrap();
+            assert!(((after.latitude - before.latitude) / 0.1 - pos.speed_latitude).abs() < 1e-4);
+            assert!(((after.distance - before.distance) / 0.1 - pos.speed_distance).abs() < 1e-6);
+        }
+    }
+}
+
 #[test]
 fn test_accuracy_various_dates() {
     // Test dates spanning different centuries
@@ -185,7 +261,7 @@ fn test_houses_accuracy() {
     // Reference: swetest -b1.1.2000 -ut12:00 -house51.5,-0.1,P
     // London coordinates
     let jd = julian::julday(2000, 1, 1, 12.0, 1);
-    let houses = calc_houses(jd, 51.5, -0.1).unwrap();
+    let houses = calc_houses(jd, 51.5, -0.1, HouseSystem::Placidus).unwrap();
 
     println!("\n=== House Calculation Test: London ===\n");
     println!("Ascendant: {:.4}°", houses.ascendant);